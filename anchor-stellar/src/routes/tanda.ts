@@ -64,7 +64,7 @@ router.post('/create', requireSoroban, asyncHandler(async (req: Request, res: Re
     });
   }
 
-  throw Errors.sorobanError(result.error || 'Failed to create tanda');
+  throw Errors.contract(result.error, 'Failed to create tanda');
 }));
 
 // ==================== JOIN TANDA ====================
@@ -93,7 +93,7 @@ router.post('/join', requireSoroban, asyncHandler(async (req: Request, res: Resp
     });
   }
 
-  throw Errors.sorobanError(result.error || 'Failed to join tanda');
+  throw Errors.contract(result.error, 'Failed to join tanda');
 }));
 
 // ==================== LIST TANDAS ====================
@@ -170,7 +170,7 @@ router.post('/:id/confirm-deposit', requireSoroban, asyncHandler(async (req: Req
     });
   }
 
-  throw Errors.sorobanError(result.error || 'Failed to confirm deposit');
+  throw Errors.contract(result.error, 'Failed to confirm deposit');
 }));
 
 // ==================== PROCESS PAYOUT / ADVANCE ====================
@@ -199,7 +199,7 @@ router.post('/:id/payout', requireSoroban, asyncHandler(async (req: Request, res
     });
   }
 
-  throw Errors.sorobanError(result.error || 'Failed to advance tanda');
+  throw Errors.contract(result.error, 'Failed to advance tanda');
 }));
 
 // ==================== LEAVE TANDA ====================
@@ -233,7 +233,7 @@ router.post('/:id/leave', requireSoroban, asyncHandler(async (req: Request, res:
     });
   }
 
-  throw Errors.sorobanError(result.error || 'Failed to leave tanda');
+  throw Errors.contract(result.error, 'Failed to leave tanda');
 }));
 
 // ==================== PAYMENT SCHEDULE ====================
@@ -259,7 +259,10 @@ router.get('/:id/schedule', requireSoroban, asyncHandler(async (req: Request, re
 
   const schedule = tanda.beneficiaryOrder.map((beneficiary: string, index: number) => ({
     cycle: index + 1,
-    dueDate: 0,
+    // Fixed-schedule tandas pay cycle n out at started_at + n * period
+    dueDate: tanda.cycleDays && tanda.startedAt
+      ? tanda.startedAt + (index + 1) * tanda.cycleDays * 86_400_000
+      : 0,
     beneficiary,
     status: index + 1 < tanda.currentCycle
       ? 'completed'
@@ -406,7 +409,7 @@ router.post('/:id/start', requireSoroban, asyncHandler(async (req: Request, res:
     });
   }
 
-  throw Errors.sorobanError(result.error || 'Failed to start tanda');
+  throw Errors.contract(result.error, 'Failed to start tanda');
 }));
 
 export default router;
@@ -20,10 +20,16 @@ import { stellarService } from './stellar.js';
 // Contract types matching lib.rs
 export interface SorobanMember {
   address: string;
+  slot: number;
   status: 'Active' | 'Received' | 'Expelled';
   position: number;
   has_deposited: boolean;
+  deposit_cycle: number;
+  paid_this_cycle: bigint;
   joined_at: number;
+  commitment: bigint;
+  shares: Record<string, number>;
+  shares_paid: Record<string, bigint>;
 }
 
 export interface SorobanTanda {
@@ -35,9 +41,39 @@ export interface SorobanTanda {
   status: 'Forming' | 'Active' | 'Completed' | 'Cancelled';
   current_cycle: number;
   total_cycles: number;
+  active_members: number;
   created_at: number;
   started_at: number;
   last_payout_at: number;
+  delinquency_days: number;
+  safety_fund_bps: number;
+  collateral_multiple: number;
+  visibility: 'Open' | 'ApprovalRequired';
+  request_expiry_days: number;
+  invite_only: boolean;
+  schedule: 'WhenAllPaid' | 'Fixed';
+  period_days: number;
+  forming_deadline: number;
+  forming_expiry: 'Cancel' | 'StartIfReady';
+  auto_start_when_full: boolean;
+  payout_order: 'JoinOrder' | 'CreatorAssigned' | 'Random' | 'Reverse' | 'Lottery' | 'Auction';
+  sealed_bids: boolean;
+  grace_days: number;
+  late_fee_bps: number;
+  late_fee_to: 'Beneficiary' | 'SafetyFund';
+  max_slots_per_member: number;
+  commitment_mode: 'Equal' | 'Pooled' | 'Proportional';
+  renewed_from: string | null;
+  renewed_as: string | null;
+}
+
+export interface SorobanAdvanceStatus {
+  can_advance: boolean;
+  will_expel_count: number;
+  will_payout: boolean;
+  beneficiary: string | null;
+  in_grace: boolean;
+  grace_ends_at: bigint;
 }
 
 // Response type for API
@@ -61,6 +97,7 @@ export interface TandaResponse {
   }[];
   beneficiaryOrder: string[];
   createdAt: number;
+  startedAt: number;
 }
 
 // Local storage for tanda tracking (since we can't list all tandas from contract)
@@ -379,27 +416,31 @@ class SorobanService {
     tandaId: string,
     walletAddress: string
   ): Promise<{ success: boolean; tanda?: TandaResponse; error?: string }> {
-    // Note: The contract doesn't have a leave function,
-    // but we can remove from local registry if still forming
     console.log('[Soroban] Leave tanda:', tandaId, walletAddress);
 
-    // Check if tanda is still forming
-    const tandaResult = await this.getTanda(tandaId);
-    if (!tandaResult.success || !tandaResult.tanda) {
-      return { success: false, error: 'Tanda not found' };
-    }
+    const params = [
+      new Address(walletAddress).toScVal(),
+      nativeToScVal(tandaId, { type: 'string' }),
+    ];
 
-    if (tandaResult.tanda.status !== 'waiting') {
-      return { success: false, error: 'Cannot leave after tanda has started' };
-    }
+    const result = await this.callContract('leave_tanda', params);
+
+    if (result.success) {
+      const tandaResult = await this.getTanda(tandaId);
 
-    // Update local registry
-    const entry = this.registry.tandas.get(tandaId);
-    if (entry) {
-      entry.participants = entry.participants.filter(p => p !== walletAddress);
+      // Update registry (a leaving creator hands the role to the next member)
+      const entry = this.registry.tandas.get(tandaId);
+      if (entry) {
+        entry.participants = entry.participants.filter(p => p !== walletAddress);
+        if (tandaResult.tanda) {
+          entry.creator = tandaResult.tanda.creator;
+        }
+      }
+
+      return tandaResult;
     }
 
-    return this.getTanda(tandaId);
+    return { success: false, error: result.error };
   }
 
   /**
@@ -435,7 +476,7 @@ class SorobanService {
       currentParticipants: rawMembers.filter((m: any) => m.status !== 'Expelled').length,
       totalCycles: rawTanda.total_cycles,
       currentCycle: rawTanda.current_cycle,
-      cycleDays: 0, // Not used in new model
+      cycleDays: Number(rawTanda.period_days), // 0 unless payouts run on a fixed schedule
       status: this.convertStatus(rawTanda.status),
       storageAccount: STELLAR_CONFIG.contractId, // Contract holds funds
       participants: rawMembers.map((m: any) => ({
@@ -449,6 +490,7 @@ class SorobanService {
         .sort((a: any, b: any) => a.position - b.position)
         .map((m: any) => m.address),
       createdAt: Number(rawTanda.created_at) * 1000, // Convert to ms
+      startedAt: Number(rawTanda.started_at) * 1000,
     };
 
     return { success: true, tanda };
@@ -552,22 +594,33 @@ class SorobanService {
     willExpelCount: number;
     willPayout: boolean;
     beneficiary: string | null;
+    inGrace: boolean;
+    graceEndsAt: number;
   }> {
     const result = await this.readContract('get_advance_status', [
       nativeToScVal(tandaId, { type: 'string' }),
     ]);
 
-    if (result.success && Array.isArray(result.result)) {
-      const [canAdvance, willExpelCount, willPayout, beneficiary] = result.result;
+    if (result.success && result.result) {
+      const status = result.result as SorobanAdvanceStatus;
       return {
-        canAdvance: canAdvance as boolean,
-        willExpelCount: willExpelCount as number,
-        willPayout: willPayout as boolean,
-        beneficiary: beneficiary as string | null,
+        canAdvance: status.can_advance,
+        willExpelCount: status.will_expel_count,
+        willPayout: status.will_payout,
+        beneficiary: status.beneficiary ?? null,
+        inGrace: status.in_grace,
+        graceEndsAt: Number(status.grace_ends_at),
       };
     }
 
-    return { canAdvance: false, willExpelCount: 0, willPayout: false, beneficiary: null };
+    return {
+      canAdvance: false,
+      willExpelCount: 0,
+      willPayout: false,
+      beneficiary: null,
+      inGrace: false,
+      graceEndsAt: 0,
+    };
   }
 }
 
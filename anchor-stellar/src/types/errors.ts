@@ -32,6 +32,10 @@ export enum ErrorCode {
   INSUFFICIENT_BALANCE = 'INSUFFICIENT_BALANCE',
   NOT_CREATOR = 'NOT_CREATOR',
   MINIMUM_MEMBERS_REQUIRED = 'MINIMUM_MEMBERS_REQUIRED',
+  WAITLIST_UNAVAILABLE = 'WAITLIST_UNAVAILABLE',
+
+  // Rejected by the tanda contract without a more specific code (400)
+  CONTRACT_ERROR = 'CONTRACT_ERROR',
 
   // Service unavailable (503)
   SOROBAN_NOT_CONFIGURED = 'SOROBAN_NOT_CONFIGURED',
@@ -119,6 +123,9 @@ export const Errors = {
   minimumMembers: (required: number, current: number) =>
     new AppError(ErrorCode.MINIMUM_MEMBERS_REQUIRED, `Minimum ${required} members required, currently ${current}`, 400),
 
+  waitlistUnavailable: () =>
+    new AppError(ErrorCode.WAITLIST_UNAVAILABLE, 'This tanda does not take a waitlist', 400),
+
   // Auth
   kycRequired: () =>
     new AppError(ErrorCode.KYC_REQUIRED, 'KYC verification required', 403),
@@ -136,6 +143,14 @@ export const Errors = {
   sorobanError: (message: string) =>
     new AppError(ErrorCode.SOROBAN_ERROR, message, 500),
 
+  // Maps a `TandaError` in a failed contract call to its API error
+  contract: (error: string | undefined, fallback: string) => {
+    const code = parseContractError(error);
+    return code === undefined
+      ? new AppError(ErrorCode.SOROBAN_ERROR, error || fallback, 500)
+      : fromContractError(code);
+  },
+
   sponsorError: (message: string) =>
     new AppError(ErrorCode.SPONSOR_ERROR, message, 500),
 
@@ -143,6 +158,139 @@ export const Errors = {
     new AppError(ErrorCode.INTERNAL_ERROR, message, 500),
 };
 
+/**
+ * TandaError codes returned by the contract (`Error(Contract, #N)`).
+ * Must match the enum in soroban-tanda/src/lib.rs.
+ */
+export enum TandaErrorCode {
+  // Setup
+  AlreadyInitialized = 1,
+  NotInitialized = 2,
+  CommissionTooHigh = 3,
+
+  // Tanda creation
+  InvalidAmount = 10,
+  InvalidMemberCount = 11,
+  InvalidDelinquencyDays = 12,
+  SafetyFundTooHigh = 13,
+  CollateralTooHigh = 14,
+  InvalidRequestExpiry = 15,
+  TooManyInviteCodes = 16,
+  InvalidPeriod = 17,
+  InvalidFormingDeadline = 18,
+  InvalidGracePeriod = 19,
+
+  // Lifecycle
+  TandaNotFound = 20,
+  NotForming = 21,
+  NotActive = 22,
+  NotCreator = 23,
+  TandaFull = 24,
+  AlreadyMember = 25,
+  NotEnoughMembers = 26,
+  RequestPending = 27,
+  RequestNotFound = 28,
+  RequestExpired = 29,
+
+  // Deposits & payouts
+  NotMember = 30,
+  MemberExpelled = 31,
+  AlreadyDeposited = 32,
+  NotAllDeposited = 33,
+  BeneficiaryNotFound = 34,
+  PayoutNotDue = 35,
+  NotScheduled = 36,
+  AlreadyConsented = 37,
+  InvalidPrepayment = 38,
+
+  // Delinquency
+  DelinquencyNotPassed = 40,
+  AlreadyExpelled = 41,
+  MemberHasDeposited = 42,
+  LateFeeTooHigh = 43,
+
+  // Invitations
+  InviteRequired = 50,
+  InvalidInviteCode = 51,
+  InviteExpired = 52,
+
+  // Forming deadline
+  NoFormingDeadline = 60,
+  FormingNotExpired = 61,
+
+  // Payout order
+  OrderNotAssignable = 70,
+  InvalidPayoutOrder = 71,
+
+  // Auctions
+  NotAuction = 80,
+  InvalidBid = 81,
+  NotEligibleToBid = 82,
+  WrongBidMode = 83,
+  BiddingClosed = 84,
+  RevealNotOpen = 85,
+  BidMismatch = 86,
+  RevealPending = 87,
+
+  // Position swaps
+  SwapNotAllowed = 90,
+  SwapNotFound = 91,
+  ListingNotFound = 92,
+  ListingExpired = 93,
+  InvalidPremium = 94,
+
+  // Waitlist
+  WaitlistUnavailable = 100,
+
+  // Slots
+  InvalidSlotLimit = 110,
+  InvalidShares = 111,
+  InvalidOwnerCount = 112,
+  SharedSlotsUnavailable = 113,
+
+  // Renewal
+  NotCompleted = 120,
+  AlreadyRenewed = 121,
+  NoReservedSeat = 122,
+  CreatorHasNoSeat = 123,
+}
+
+// Contract errors that have a matching API error
+const CONTRACT_ERRORS: Partial<Record<TandaErrorCode, () => AppError>> = {
+  [TandaErrorCode.InvalidAmount]: () =>
+    new AppError(ErrorCode.INVALID_AMOUNT, 'Invalid amount', 400),
+  [TandaErrorCode.InvalidMemberCount]: () =>
+    new AppError(ErrorCode.INVALID_PARTICIPANTS, 'Invalid number of participants', 400),
+  [TandaErrorCode.TandaNotFound]: () =>
+    new AppError(ErrorCode.TANDA_NOT_FOUND, 'Tanda not found', 404),
+  [TandaErrorCode.NotForming]: Errors.tandaNotForming,
+  [TandaErrorCode.NotActive]: Errors.tandaNotActive,
+  [TandaErrorCode.NotCreator]: Errors.notCreator,
+  [TandaErrorCode.TandaFull]: Errors.tandaFull,
+  [TandaErrorCode.AlreadyMember]: Errors.alreadyParticipant,
+  [TandaErrorCode.NotEnoughMembers]: () =>
+    new AppError(ErrorCode.MINIMUM_MEMBERS_REQUIRED, 'Not enough members to start', 400),
+  [TandaErrorCode.NotMember]: Errors.notParticipant,
+  [TandaErrorCode.AlreadyDeposited]: Errors.alreadyDeposited,
+  [TandaErrorCode.WaitlistUnavailable]: Errors.waitlistUnavailable,
+};
+
+// Extract the contract error code from a simulation or host error message
+export function parseContractError(error?: string): number | undefined {
+  const match = error?.match(/Error\(Contract, #(\d+)\)/);
+  return match ? Number(match[1]) : undefined;
+}
+
+export function fromContractError(code: number): AppError {
+  const mapped = CONTRACT_ERRORS[code as TandaErrorCode];
+  if (mapped) {
+    return mapped();
+  }
+  const name = TandaErrorCode[code] ?? 'Unknown';
+  return new AppError(ErrorCode.CONTRACT_ERROR, `Contract rejected the call: ${name}`, 400,
+    { contractError: name, code });
+}
+
 // Type guard for AppError
 export function isAppError(error: unknown): error is AppError {
   return error instanceof AppError;
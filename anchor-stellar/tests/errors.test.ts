@@ -3,7 +3,7 @@
  */
 
 import { describe, it, expect } from 'vitest';
-import { AppError, ErrorCode, Errors, isAppError } from '../src/types/errors.js';
+import { AppError, ErrorCode, Errors, TandaErrorCode, isAppError, parseContractError } from '../src/types/errors.js';
 
 describe('Error System', () => {
   describe('AppError', () => {
@@ -141,4 +141,33 @@ describe('Error System', () => {
       expect(error.statusCode).toBe(500);
     });
   });
+
+  describe('Contract Errors', () => {
+    it('should parse the code from a simulation error', () => {
+      expect(parseContractError('HostError: Error(Contract, #24)')).toBe(TandaErrorCode.TandaFull);
+      expect(parseContractError('Transaction failed')).toBeUndefined();
+      expect(parseContractError(undefined)).toBeUndefined();
+    });
+
+    it('should map contract codes to API errors', () => {
+      expect(Errors.contract('Error(Contract, #24)', 'Failed').code).toBe(ErrorCode.TANDA_FULL);
+      expect(Errors.contract('Error(Contract, #25)', 'Failed').code).toBe(ErrorCode.ALREADY_PARTICIPANT);
+      expect(Errors.contract('Error(Contract, #20)', 'Failed').statusCode).toBe(404);
+      expect(Errors.contract('Error(Contract, #100)', 'Failed').code).toBe(ErrorCode.WAITLIST_UNAVAILABLE);
+    });
+
+    it('should name contract codes without an API error', () => {
+      const error = Errors.contract('Error(Contract, #40)', 'Failed');
+      expect(error.code).toBe(ErrorCode.CONTRACT_ERROR);
+      expect(error.statusCode).toBe(400);
+      expect(error.details).toEqual({ contractError: 'DelinquencyNotPassed', code: 40 });
+    });
+
+    it('should fall back to a soroban error', () => {
+      const error = Errors.contract(undefined, 'Failed to join tanda');
+      expect(error.code).toBe(ErrorCode.SOROBAN_ERROR);
+      expect(error.message).toBe('Failed to join tanda');
+      expect(error.statusCode).toBe(500);
+    });
+  });
 });
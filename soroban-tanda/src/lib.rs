@@ -11,8 +11,8 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype,
-    Address, BytesN, Env, Map, String, Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype,
+    Address, Bytes, BytesN, Env, Map, String, Symbol, Vec,
     token::Client as TokenClient,
 };
 
@@ -28,6 +28,138 @@ const MAX_DELINQUENCY_DAYS: u64 = 14;
 const SECONDS_PER_DAY: u64 = 86400;
 /// Fixed fee for creating a tanda (0.10 EURC = 1_000_000 with 7 decimals)
 const CREATE_TANDA_FEE: i128 = 1_000_000;
+/// Maximum share of each deposit that can go to the safety fund (10%)
+const MAX_SAFETY_FUND_BPS: u32 = 1000;
+/// Maximum collateral bond, as a multiple of the per-cycle amount
+const MAX_COLLATERAL_MULTIPLE: u32 = 12;
+/// Default and maximum lifetime of a pending join request
+const DEFAULT_REQUEST_EXPIRY_DAYS: u64 = 7;
+const MAX_REQUEST_EXPIRY_DAYS: u64 = 30;
+/// Maximum invite codes registered on a tanda at once
+const MAX_INVITE_CODES: u32 = 20;
+/// Longest cycle period for tandas on a fixed schedule
+const MAX_PERIOD_DAYS: u64 = 90;
+/// Fewest members a tanda can start with
+const MIN_MEMBERS: u32 = 2;
+/// Longest a tanda can stay forming when it has a forming deadline
+const MAX_FORMING_DAYS: u64 = 90;
+/// Longest grace window after the delinquency deadline
+const MAX_GRACE_DAYS: u64 = 14;
+/// Highest late fee (10% of the amount)
+const MAX_LATE_FEE_BPS: u32 = 1000;
+/// Time sealed bidders have to reveal once bidding on a cycle closes
+const REVEAL_WINDOW: u64 = SECONDS_PER_DAY;
+/// Ledgers closed per day (~5 seconds per ledger)
+const DAY_IN_LEDGERS: u32 = 17280;
+/// Instance storage holds the global config every tanda depends on, so it is
+/// kept alive at least as long as the longest-lived tanda entry
+const INSTANCE_BUMP_AMOUNT: u32 = 120 * DAY_IN_LEDGERS;
+const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+/// Tanda entries are extended to 120 days once they drop below 90, so even
+/// several 14-day delinquency windows without activity can't archive them
+const TANDA_BUMP_AMOUNT: u32 = 120 * DAY_IN_LEDGERS;
+const TANDA_LIFETIME_THRESHOLD: u32 = 90 * DAY_IN_LEDGERS;
+
+// ==================== ERRORS ====================
+
+/// Contract errors. Codes are stable: clients map them to their own error codes.
+/// Not exported to the contract spec, which caps error enums at 50 cases; the
+/// codes are listed in the README and mirrored by the backend.
+#[contracterror(export = false)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum TandaError {
+    // Setup
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    CommissionTooHigh = 3,
+
+    // Tanda creation
+    InvalidAmount = 10,
+    InvalidMemberCount = 11,
+    InvalidDelinquencyDays = 12,
+    SafetyFundTooHigh = 13,
+    CollateralTooHigh = 14,
+    InvalidRequestExpiry = 15,
+    TooManyInviteCodes = 16,
+    InvalidPeriod = 17,
+    InvalidFormingDeadline = 18,
+    InvalidGracePeriod = 19,
+
+    // Lifecycle
+    TandaNotFound = 20,
+    NotForming = 21,
+    NotActive = 22,
+    NotCreator = 23,
+    TandaFull = 24,
+    AlreadyMember = 25,
+    NotEnoughMembers = 26,
+    RequestPending = 27,
+    RequestNotFound = 28,
+    RequestExpired = 29,
+
+    // Deposits & payouts
+    NotMember = 30,
+    MemberExpelled = 31,
+    AlreadyDeposited = 32,
+    NotAllDeposited = 33,
+    BeneficiaryNotFound = 34,
+    PayoutNotDue = 35,
+    NotScheduled = 36,
+    AlreadyConsented = 37,
+    InvalidPrepayment = 38,
+
+    // Delinquency
+    DelinquencyNotPassed = 40,
+    AlreadyExpelled = 41,
+    MemberHasDeposited = 42,
+    LateFeeTooHigh = 43,
+
+    // Invitations
+    InviteRequired = 50,
+    InvalidInviteCode = 51,
+    InviteExpired = 52,
+
+    // Forming deadline
+    NoFormingDeadline = 60,
+    FormingNotExpired = 61,
+
+    // Payout order
+    OrderNotAssignable = 70,
+    InvalidPayoutOrder = 71,
+
+    // Auctions
+    NotAuction = 80,
+    InvalidBid = 81,
+    NotEligibleToBid = 82,
+    WrongBidMode = 83,
+    BiddingClosed = 84,
+    RevealNotOpen = 85,
+    BidMismatch = 86,
+    RevealPending = 87,
+
+    // Position swaps
+    SwapNotAllowed = 90,
+    SwapNotFound = 91,
+    ListingNotFound = 92,
+    ListingExpired = 93,
+    InvalidPremium = 94,
+
+    // Waitlist
+    WaitlistUnavailable = 100,
+
+    // Slots
+    InvalidSlotLimit = 110,
+    InvalidShares = 111,
+    InvalidOwnerCount = 112,
+    SharedSlotsUnavailable = 113,
+
+    // Renewal
+    NotCompleted = 120,
+    AlreadyRenewed = 121,
+    NoReservedSeat = 122,
+    CreatorHasNoSeat = 123,
+}
 
 // ==================== DATA TYPES ====================
 
@@ -50,15 +182,90 @@ pub enum MemberStatus {
     Expelled,   // Removed for non-payment
 }
 
+/// Who can join a tanda
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Visibility {
+    Open,               // Anyone can join while forming
+    ApprovalRequired,   // Joining files a request the creator approves or rejects
+}
+
+/// When payouts happen
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Schedule {
+    WhenAllPaid,    // As soon as every member has deposited
+    Fixed,          // On a fixed due date every `period_days`
+}
+
+/// How the payout order is settled when the tanda starts
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PayoutOrder {
+    JoinOrder,          // First to join is paid first
+    CreatorAssigned,    // Order submitted by the creator with `assign_payout_order`
+    Random,             // Shuffled with the ledger PRNG at start
+    Reverse,            // Last to join is paid first
+    Lottery,            // Each cycle's beneficiary is drawn from those not yet paid
+    Auction,            // Each cycle goes to the highest discount bid (see `place_bid`)
+}
+
+/// Who receives late fees paid during the grace window
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LateFeeRecipient {
+    Beneficiary,    // Added to the cycle's pot
+    SafetyFund,     // Added to the tanda's safety fund
+}
+
+/// What each member puts in per cycle, and what each beneficiary receives
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CommitmentMode {
+    Equal,          // Everyone pays `amount`
+    Pooled,         // Each member pays their own commitment; the payout is the sum of all of them
+    Proportional,   // Payouts are `commitment` x slots; each cycle's dues are scaled to the beneficiary
+}
+
+/// Payout order of a renewed tanda's reserved seats
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RenewalOrder {
+    Keep,           // Same order as the previous round
+    Rotate,         // Previous round's first beneficiary goes last, everyone else moves up
+}
+
+/// What happens to a tanda still forming at its deadline
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FormingExpiry {
+    Cancel,         // Cancel it and refund the creation fee
+    StartIfReady,   // Start it with whoever joined, or cancel if too few did
+}
+
 /// A tanda member
 #[contracttype]
 #[derive(Clone, Debug)]
 pub struct Member {
     pub address: Address,
+    pub slot: u32,               // Which of the address's slots this is (0 = first joined)
     pub status: MemberStatus,
     pub position: u32,           // Payout order (0 = first to receive)
     pub has_deposited: bool,     // Deposited for current cycle
+    pub deposit_cycle: u32,      // Cycle the deposit fields were set in (older values are stale)
+    pub paid_this_cycle: i128,   // Paid toward the current cycle so far (installments)
     pub joined_at: u64,
+    pub commitment: i128,        // Amount committed per cycle (`amount` unless the tanda is weighted)
+    pub shares: Map<Address, u32>,       // Owners of a shared slot and their bps (empty = held alone)
+    pub shares_paid: Map<Address, i128>, // Paid by each owner of a shared slot toward the current cycle
+}
+
+/// One payout slot: the address holding it and which of its slots it is
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SlotId {
+    pub address: Address,
+    pub slot: u32,
 }
 
 /// A complete tanda
@@ -69,14 +276,150 @@ pub struct Tanda {
     pub name: String,
     pub creator: Address,
     pub amount: i128,            // Amount per cycle in EURC (7 decimals)
-    pub max_members: u32,
+    pub max_members: u32,        // Slots available (one per member unless members hold several)
     pub status: TandaStatus,
     pub current_cycle: u32,      // Which payout we're on (1-indexed)
-    pub total_cycles: u32,       // = number of active members
+    pub total_cycles: u32,       // = number of active slots
+    pub active_members: u32,     // Slots not expelled
     pub created_at: u64,
     pub started_at: u64,
     pub last_payout_at: u64,     // Timestamp of last payout (for delinquency calc)
     pub delinquency_days: u64,   // Configurable days before expulsion (3-14)
+    pub safety_fund_bps: u32,    // Extra share of each deposit paid into the safety fund
+    pub collateral_multiple: u32, // Bond each member escrows, in multiples of `amount` (0 = none)
+    pub visibility: Visibility,
+    pub request_expiry_days: u64, // Days a join request stays pending before it lapses
+    pub invite_only: bool,       // Joining requires one of the creator's invite codes
+    pub schedule: Schedule,
+    pub period_days: u64,        // Cycle length on a fixed schedule (0 = no schedule)
+    pub forming_deadline: u64,   // Timestamp after which anyone can resolve a forming tanda (0 = none)
+    pub forming_expiry: FormingExpiry,
+    pub auto_start_when_full: bool, // Start as soon as the last slot is filled
+    pub payout_order: PayoutOrder,
+    pub sealed_bids: bool,       // Auction bids are committed as hashes and revealed after `close_bids`
+    pub grace_days: u64,         // Days after the delinquency deadline a member can still pay late
+    pub late_fee_bps: u32,       // Late fee on deposits made during the grace window
+    pub late_fee_to: LateFeeRecipient,
+    pub max_slots_per_member: u32, // Slots one address may hold
+    pub commitment_mode: CommitmentMode,
+    pub renewed_from: Option<String>, // Tanda this one renews
+    pub renewed_as: Option<String>,   // Successor created by `renew_tanda`
+}
+
+/// Settings chosen when creating a tanda
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct TandaConfig {
+    pub delinquency_days: u64,   // Days before a non-paying member can be expelled (3-14)
+    pub safety_fund_bps: u32,    // Safety fund share on top of each deposit (0 = no fund)
+    pub collateral_multiple: u32, // Bond posted on joining, as a multiple of `amount` (0 = none)
+    pub visibility: Visibility,   // Open, or join requests need the creator's approval
+    pub request_expiry_days: u64, // Days a join request stays pending (1-30)
+    pub invite_codes: Vec<InviteCode>, // Codes required to join (empty = no code needed)
+    pub schedule: Schedule,       // Pay out when everyone has paid, or on fixed due dates
+    pub period_days: u64,         // Cycle length for a fixed schedule (1-90, ignored otherwise)
+    pub forming_days: u64,        // Days the tanda may stay forming (0 = no deadline, max 90)
+    pub forming_expiry: FormingExpiry, // Policy applied at the forming deadline
+    pub auto_start_when_full: bool, // Start the tanda when the last slot is filled
+    pub payout_order: PayoutOrder,  // How the payout order is settled at start
+    pub sealed_bids: bool,        // Sealed (commit/reveal) auction bids (ignored unless `Auction`)
+    pub grace_days: u64,          // Late payment window before expulsion (0 = none, max 14)
+    pub late_fee_bps: u32,        // Late fee in bps of `amount` (max 10%)
+    pub late_fee_to: LateFeeRecipient, // Where late fees go
+    pub max_slots_per_member: u32, // Slots one address may hold (1 to max_members)
+    pub commitment_mode: CommitmentMode, // Same amount for everyone, or per-member commitments
+}
+
+/// An invite code registered by the creator. Only the hash is stored on-chain;
+/// invitees present the code itself when joining.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct InviteCode {
+    pub hash: BytesN<32>,        // sha256 of the code
+    pub single_use: bool,        // Revoked once someone joins with it
+    pub expires_at: u64,         // Timestamp the code stops working (0 = never)
+}
+
+/// A pending request to join a tanda that requires approval
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct JoinRequest {
+    pub address: Address,
+    pub requested_at: u64,
+    pub expires_at: u64,
+    pub bond: i128,              // Collateral escrowed with the request, returned unless approved
+}
+
+/// Someone waiting to take over the slot of a member expelled before receiving
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct WaitlistEntry {
+    pub address: Address,
+    pub joined_at: u64,
+    pub held: i128,              // Escrowed to cover the catch-up, open cycle and collateral
+    pub commission: i128,        // Commission on the open cycle's deposit (included in `held`)
+}
+
+/// A seat held in a renewed tanda for a slot of the previous round
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Reservation {
+    pub address: Address,        // Holder of the slot
+    pub shares: Map<Address, u32>, // Co-owners of a shared slot and their bps (empty = held alone)
+    pub commitment: i128,        // Commitment carried over from the previous round
+    pub slot: Option<u32>,       // Slot taken once confirmed (None = still pending)
+}
+
+/// Kind of safety fund movement
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FundMovement {
+    Contribution,   // Collected from deposits during a cycle
+    TopUp,          // Covered a payout shortfall left by a defaulter
+    Refund,         // Returned to a member in good standing at completion
+    LateFee,        // Paid by a member depositing during the grace window
+    Forfeit,        // Share of a payout that belonged to a co-owner who defaulted
+}
+
+/// An entry in a tanda's safety fund history
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct FundEntry {
+    pub cycle: u32,
+    pub movement: FundMovement,
+    pub account: Option<Address>, // Receiver of a top-up or refund (None for contributions)
+    pub amount: i128,
+    pub timestamp: u64,
+}
+
+/// Cycles a member paid ahead, and the commission held for them
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Prepayment {
+    pub cycles: u32,
+    pub commission: i128,   // Commission for all remaining cycles, paid out cycle by cycle
+}
+
+/// Outcome of a cycle's discount auction
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AuctionResult {
+    pub winner: Address,
+    pub discount: i128,     // Winning bid (0 if nobody bid)
+    pub dividend: i128,     // Share of the discount paid to each other contributor
+    pub recipients: u32,    // Contributors who received the dividend
+}
+
+/// What `advance` would do if called now (for the "Advance" button)
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AdvanceStatus {
+    pub can_advance: bool,
+    pub will_expel_count: u32,
+    pub will_payout: bool,
+    pub beneficiary: Option<Address>, // Who the payout would go to (None if picked at payout)
+    pub in_grace: bool,          // Late members can still deposit, with a late fee
+    pub grace_ends_at: u64,      // Nobody is expelled before this
 }
 
 /// Storage keys
@@ -88,11 +431,37 @@ pub enum DataKey {
     CommissionAddress,
     CommissionBps,              // Commission in basis points (100 = 1%)
     TandaCount,
+    TotalEscrow,                // Sum of all EURC owed to tandas (escrow + safety funds)
 
     // Per tanda
     Tanda(String),              // Tanda data by ID
-    Members(String),            // Vec<Member> - members of tanda
+    Member(String, Address),    // Member record (their first slot)
+    MemberSlot(String, Address, u32), // Member record of any further slot
+    CoOwner(String, Address),   // SlotId - the shared slot a co-owner holds a share of
+    MemberList(String),         // Vec<Address> - every member in join order
+    Positions(String),          // Vec<SlotId> - slot at each payout position
+    Deposits(String, u32),      // Number of slots deposited for in a cycle
     SafetyFund(String),         // Accumulated safety fund for tanda
+    SafetyFundShares(String),   // Map<Address, i128> - what each member paid into the fund
+    SafetyFundHistory(String),  // Vec<FundEntry> - fund movements
+    Collateral(String),         // Map<Address, i128> - bonds held per member
+    JoinRequests(String),       // Vec<JoinRequest> - pending join requests
+    InviteCodes(String),        // Map<BytesN<32>, InviteCode> - codes by hash
+    EarlyConsents(String, u32), // Vec<Address> - members agreeing to pay a cycle out before it is due
+    CreationFee(String),        // Creation fee held until a tanda with a forming deadline starts
+    TtlExtendedAt(String),      // Ledger sequence of the last TTL extension
+    Escrow(String),             // EURC held for the tanda's open cycle
+    Contribution(String, u32, Address), // What a member paid in a cycle
+    Bids(String, u32),          // Map<Address, i128> - discount bids for a cycle (auction mode)
+    AuctionResult(String, u32), // AuctionResult - how a cycle's auction was settled
+    SealedBids(String, u32),    // Map<Address, BytesN<32>> - sealed bids not yet revealed
+    RevealDeadline(String, u32), // When the reveal window of a cycle's sealed bids ends
+    SwapProposals(String),      // Map<Address, Address> - proposer -> member they offer to swap with
+    Listings(String),           // Map<Address, i128> - positions for sale and their asking premium
+    Prepaid(String),            // Map<Address, Prepayment> - cycles members paid in advance
+    Waitlist(String),           // Vec<WaitlistEntry> - replacements for expelled members, in order
+    Reservations(String),       // Vec<Reservation> - seats held for a renewal, in payout order
+    CycleScale(String),         // (i128, i128) - commitment scale of a proportional tanda's open cycle
 }
 
 // ==================== CONTRACT ====================
@@ -111,9 +480,9 @@ impl TandaContract {
         eurc_token: Address,
         commission_address: Address,
         commission_bps: u32,
-    ) {
+    ) -> Result<(), TandaError> {
         if env.storage().instance().has(&DataKey::Admin) {
-            panic!("already initialized");
+            return Err(TandaError::AlreadyInitialized);
         }
 
         env.storage().instance().set(&DataKey::Admin, &admin);
@@ -121,109 +490,185 @@ impl TandaContract {
         env.storage().instance().set(&DataKey::CommissionAddress, &commission_address);
         env.storage().instance().set(&DataKey::CommissionBps, &commission_bps);
         env.storage().instance().set(&DataKey::TandaCount, &0u32);
+        Self::extend_instance_ttl(&env);
+
+        Ok(())
     }
 
     // ==================== ADMIN FUNCTIONS ====================
 
     /// Upgrade the contract code (admin only)
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), TandaError> {
         let admin: Address = env.storage().instance()
             .get(&DataKey::Admin)
-            .expect("not initialized");
+            .ok_or(TandaError::NotInitialized)?;
         admin.require_auth();
 
         env.deployer().update_current_contract_wasm(new_wasm_hash);
+
+        Ok(())
     }
 
     /// Update commission settings (admin only)
-    pub fn set_commission(env: Env, commission_address: Address, commission_bps: u32) {
+    pub fn set_commission(
+        env: Env,
+        commission_address: Address,
+        commission_bps: u32,
+    ) -> Result<(), TandaError> {
         let admin: Address = env.storage().instance()
             .get(&DataKey::Admin)
-            .expect("not initialized");
+            .ok_or(TandaError::NotInitialized)?;
         admin.require_auth();
 
         if commission_bps > 1000 {
-            panic!("commission too high (max 10%)");
+            return Err(TandaError::CommissionTooHigh);
         }
 
         env.storage().instance().set(&DataKey::CommissionAddress, &commission_address);
         env.storage().instance().set(&DataKey::CommissionBps, &commission_bps);
+        Self::extend_instance_ttl(&env);
+
+        Ok(())
     }
 
     /// Transfer admin role (admin only)
-    pub fn set_admin(env: Env, new_admin: Address) {
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), TandaError> {
         let admin: Address = env.storage().instance()
             .get(&DataKey::Admin)
-            .expect("not initialized");
+            .ok_or(TandaError::NotInitialized)?;
         admin.require_auth();
 
         env.storage().instance().set(&DataKey::Admin, &new_admin);
+        Self::extend_instance_ttl(&env);
+
+        Ok(())
     }
 
     /// Get current admin
-    pub fn get_admin(env: Env) -> Address {
+    pub fn get_admin(env: Env) -> Result<Address, TandaError> {
         env.storage().instance()
             .get(&DataKey::Admin)
-            .expect("not initialized")
+            .ok_or(TandaError::NotInitialized)
     }
 
     // ==================== TANDA LIFECYCLE ====================
 
-    /// Create a new tanda with optional configurable delinquency days
+    /// Create a new tanda with the default configuration
     pub fn create_tanda(
         env: Env,
         creator: Address,
         name: String,
         amount: i128,
         max_members: u32,
-    ) -> String {
-        Self::create_tanda_with_config(env, creator, name, amount, max_members, None)
+    ) -> Result<String, TandaError> {
+        let config = Self::default_config(env.clone());
+        Self::create_tanda_with_config(env, creator, name, amount, max_members, config)
+    }
+
+    /// Default settings used by `create_tanda` (a starting point for custom configs)
+    pub fn default_config(env: Env) -> TandaConfig {
+        TandaConfig {
+            delinquency_days: DEFAULT_DELINQUENCY_DAYS,
+            safety_fund_bps: 0,
+            collateral_multiple: 0,
+            visibility: Visibility::Open,
+            request_expiry_days: DEFAULT_REQUEST_EXPIRY_DAYS,
+            invite_codes: Vec::new(&env),
+            schedule: Schedule::WhenAllPaid,
+            period_days: 0,
+            forming_days: 0,
+            forming_expiry: FormingExpiry::Cancel,
+            auto_start_when_full: false,
+            payout_order: PayoutOrder::JoinOrder,
+            sealed_bids: false,
+            grace_days: 0,
+            late_fee_bps: 0,
+            late_fee_to: LateFeeRecipient::Beneficiary,
+            max_slots_per_member: 1,
+            commitment_mode: CommitmentMode::Equal,
+        }
     }
 
-    /// Create a new tanda with configurable delinquency days
+    /// Create a new tanda with custom settings
     pub fn create_tanda_with_config(
         env: Env,
         creator: Address,
         name: String,
         amount: i128,
         max_members: u32,
-        delinquency_days: Option<u64>,
-    ) -> String {
+        config: TandaConfig,
+    ) -> Result<String, TandaError> {
         creator.require_auth();
 
         // Validate amount
         if amount <= 0 {
-            panic!("amount must be positive");
+            return Err(TandaError::InvalidAmount);
         }
-        if max_members < 2 || max_members > 12 {
-            panic!("members must be 2-12");
+        if !(2..=12).contains(&max_members) {
+            return Err(TandaError::InvalidMemberCount);
         }
 
         // Validate delinquency days (3-14 days)
-        let days = delinquency_days.unwrap_or(DEFAULT_DELINQUENCY_DAYS);
-        if days < MIN_DELINQUENCY_DAYS || days > MAX_DELINQUENCY_DAYS {
-            panic!("delinquency_days must be 3-14");
+        let days = config.delinquency_days;
+        if !(MIN_DELINQUENCY_DAYS..=MAX_DELINQUENCY_DAYS).contains(&days) {
+            return Err(TandaError::InvalidDelinquencyDays);
+        }
+        if config.safety_fund_bps > MAX_SAFETY_FUND_BPS {
+            return Err(TandaError::SafetyFundTooHigh);
+        }
+        if config.collateral_multiple > MAX_COLLATERAL_MULTIPLE {
+            return Err(TandaError::CollateralTooHigh);
+        }
+        if !(1..=MAX_REQUEST_EXPIRY_DAYS).contains(&config.request_expiry_days) {
+            return Err(TandaError::InvalidRequestExpiry);
+        }
+        if config.invite_codes.len() > MAX_INVITE_CODES {
+            return Err(TandaError::TooManyInviteCodes);
+        }
+        let period_days = match config.schedule {
+            Schedule::WhenAllPaid => 0,
+            Schedule::Fixed => config.period_days,
+        };
+        if config.schedule == Schedule::Fixed && !(1..=MAX_PERIOD_DAYS).contains(&period_days) {
+            return Err(TandaError::InvalidPeriod);
+        }
+        if config.forming_days > MAX_FORMING_DAYS {
+            return Err(TandaError::InvalidFormingDeadline);
+        }
+        if config.grace_days > MAX_GRACE_DAYS {
+            return Err(TandaError::InvalidGracePeriod);
+        }
+        if config.late_fee_bps > MAX_LATE_FEE_BPS {
+            return Err(TandaError::LateFeeTooHigh);
+        }
+        if !(1..=max_members).contains(&config.max_slots_per_member) {
+            return Err(TandaError::InvalidSlotLimit);
+        }
+        // Proportional dues follow the beneficiary, who must be known before the payout
+        if config.commitment_mode == CommitmentMode::Proportional
+            && matches!(config.payout_order, PayoutOrder::Lottery | PayoutOrder::Auction)
+        {
+            return Err(TandaError::InvalidPayoutOrder);
         }
 
         // Charge creation fee (0.10 EURC)
         let eurc_token: Address = env.storage().instance()
             .get(&DataKey::EurcToken)
-            .expect("not initialized");
+            .ok_or(TandaError::NotInitialized)?;
         let commission_addr: Address = env.storage().instance()
             .get(&DataKey::CommissionAddress)
-            .expect("not initialized");
+            .ok_or(TandaError::NotInitialized)?;
 
+        // With a forming deadline the fee is held until the tanda starts, so it
+        // can be refunded if the tanda never gets going
         let token = TokenClient::new(&env, &eurc_token);
-        token.transfer(&creator, &commission_addr, &CREATE_TANDA_FEE);
-
-        // Generate ID
-        let count: u32 = env.storage().instance()
-            .get(&DataKey::TandaCount)
-            .unwrap_or(0);
-        let new_count = count + 1;
-        env.storage().instance().set(&DataKey::TandaCount, &new_count);
+        if config.forming_days > 0 {
+            token.transfer(&creator, &env.current_contract_address(), &CREATE_TANDA_FEE);
+        } else {
+            token.transfer(&creator, &commission_addr, &CREATE_TANDA_FEE);
+        }
 
-        let id = Self::generate_id(&env, new_count);
+        let id = Self::next_tanda_id(&env);
         let now = env.ledger().timestamp();
 
         // Create tanda
@@ -236,766 +681,6144 @@ impl TandaContract {
             status: TandaStatus::Forming,
             current_cycle: 0,
             total_cycles: 0,
+            active_members: 1,
             created_at: now,
             started_at: 0,
             last_payout_at: 0,
             delinquency_days: days,
+            safety_fund_bps: config.safety_fund_bps,
+            collateral_multiple: config.collateral_multiple,
+            visibility: config.visibility,
+            request_expiry_days: config.request_expiry_days,
+            invite_only: !config.invite_codes.is_empty(),
+            schedule: config.schedule,
+            period_days,
+            forming_deadline: if config.forming_days > 0 {
+                now + config.forming_days * SECONDS_PER_DAY
+            } else {
+                0
+            },
+            forming_expiry: config.forming_expiry,
+            auto_start_when_full: config.auto_start_when_full,
+            sealed_bids: config.sealed_bids && config.payout_order == PayoutOrder::Auction,
+            payout_order: config.payout_order,
+            grace_days: config.grace_days,
+            late_fee_bps: config.late_fee_bps,
+            late_fee_to: config.late_fee_to,
+            max_slots_per_member: config.max_slots_per_member,
+            commitment_mode: config.commitment_mode,
+            renewed_from: None,
+            renewed_as: None,
         };
 
-        // Creator is first member
-        let member = Member {
-            address: creator.clone(),
-            status: MemberStatus::Active,
-            position: 0,
-            has_deposited: false,
-            joined_at: now,
-        };
-
-        let mut members: Vec<Member> = Vec::new(&env);
-        members.push_back(member);
-
         // Save
         env.storage().persistent().set(&DataKey::Tanda(id.clone()), &tanda);
-        env.storage().persistent().set(&DataKey::Members(id.clone()), &members);
         env.storage().persistent().set(&DataKey::SafetyFund(id.clone()), &0i128);
 
+        if tanda.forming_deadline > 0 {
+            env.storage().persistent().set(&DataKey::CreationFee(id.clone()), &CREATE_TANDA_FEE);
+            Self::adjust_total_escrow(&env, CREATE_TANDA_FEE);
+        }
+
+        if tanda.invite_only {
+            let mut codes = Map::new(&env);
+            for code in config.invite_codes.iter() {
+                codes.set(code.hash.clone(), code);
+            }
+            env.storage().persistent().set(&DataKey::InviteCodes(id.clone()), &codes);
+        }
+
+        // Creator is first member
+        Self::add_slot(&env, &tanda, &creator, 0, Map::new(&env));
+
+        // The creator posts the same bond as everyone else
+        Self::post_collateral(&env, &tanda, &creator, Self::slot_bond(&tanda))?;
+        Self::extend_members_ttl(&env, &tanda);
+
         env.events().publish(
             (Symbol::new(&env, "tanda_created"), creator),
             id.clone(),
         );
 
-        id
+        Ok(id)
     }
 
-    /// Join a tanda (while in Forming status)
-    pub fn join_tanda(env: Env, user: Address, tanda_id: String) {
+    /// Join a tanda (while in Forming status). In tandas that require approval
+    /// this files a join request instead, escrowing the member's bond with it.
+    /// A member calling again takes one more slot, up to the tanda's cap.
+    pub fn join_tanda(env: Env, user: Address, tanda_id: String) -> Result<(), TandaError> {
         user.require_auth();
 
-        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id);
-
-        if tanda.status != TandaStatus::Forming {
-            panic!("tanda not accepting members");
-        }
-
-        let mut members: Vec<Member> = Self::get_members_internal(&env, &tanda_id);
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
 
-        if members.len() >= tanda.max_members {
-            panic!("tanda is full");
+        if tanda.invite_only && !Self::is_member(&env, &tanda_id, &user) {
+            return Err(TandaError::InviteRequired);
         }
 
-        // Check not already member
-        for m in members.iter() {
-            if m.address == user {
-                panic!("already a member");
-            }
-        }
+        Self::join_internal(&env, &user, tanda)
+    }
 
-        // Add member
-        let now = env.ledger().timestamp();
-        let member = Member {
-            address: user.clone(),
-            status: MemberStatus::Active,
-            position: members.len() as u32,
-            has_deposited: false,
-            joined_at: now,
-        };
+    /// Join an invite-only tanda with one of the creator's invite codes
+    pub fn join_with_code(
+        env: Env,
+        user: Address,
+        tanda_id: String,
+        code: Bytes,
+    ) -> Result<(), TandaError> {
+        user.require_auth();
 
-        members.push_back(member);
-        env.storage().persistent().set(&DataKey::Members(tanda_id.clone()), &members);
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+        Self::redeem_invite_code(&env, &tanda_id, &code)?;
 
-        env.events().publish(
-            (Symbol::new(&env, "member_joined"), user),
-            tanda_id,
-        );
+        Self::join_internal(&env, &user, tanda)
     }
 
-    /// Start the tanda (creator only, requires at least 2 members)
-    pub fn start_tanda(env: Env, caller: Address, tanda_id: String) {
-        caller.require_auth();
+    /// Join with one slot split between several people (`user` and their
+    /// co-owners, who all sign). `shares` gives each owner's share in basis
+    /// points, adding up to 100%: each pays that share of every deposit and
+    /// receives that share of the slot's payout. Open tandas only.
+    pub fn join_shared(
+        env: Env,
+        user: Address,
+        tanda_id: String,
+        shares: Map<Address, u32>,
+    ) -> Result<(), TandaError> {
+        for owner in shares.keys().iter() {
+            owner.require_auth();
+        }
 
-        let mut tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id);
+        let mut tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
 
-        if tanda.creator != caller {
-            panic!("only creator can start");
+        if tanda.invite_only || tanda.visibility == Visibility::ApprovalRequired {
+            return Err(TandaError::SharedSlotsUnavailable);
         }
         if tanda.status != TandaStatus::Forming {
-            panic!("tanda not in forming state");
+            return Err(TandaError::NotForming);
         }
-
-        let members: Vec<Member> = Self::get_members_internal(&env, &tanda_id);
-        let member_count = members.len() as u32;
-
-        if member_count < 2 {
-            panic!("need at least 2 members");
+        if Self::seats_taken(&env, &tanda) >= tanda.max_members {
+            return Err(TandaError::TandaFull);
         }
 
-        // Start tanda
-        let now = env.ledger().timestamp();
-        tanda.status = TandaStatus::Active;
-        tanda.started_at = now;
-        tanda.last_payout_at = now; // Start the 6-day clock
-        tanda.current_cycle = 1;
-        tanda.total_cycles = member_count;
+        // At least two owners, `user` among them, with shares adding up to 100%
+        if shares.len() < 2 || !shares.contains_key(user.clone()) {
+            return Err(TandaError::InvalidOwnerCount);
+        }
+        let mut total_bps: u32 = 0;
+        for (owner, bps) in shares.iter() {
+            if bps == 0 {
+                return Err(TandaError::InvalidShares);
+            }
+            if Self::is_member(&env, &tanda_id, &owner) {
+                return Err(TandaError::AlreadyMember);
+            }
+            total_bps = total_bps.checked_add(bps).ok_or(TandaError::InvalidShares)?;
+        }
+        if total_bps != 10000 {
+            return Err(TandaError::InvalidShares);
+        }
 
+        let member = Self::add_slot(&env, &tanda, &user, 0, shares.clone());
+        Self::link_co_owners(&env, &tanda_id, &member);
+        tanda.active_members += 1;
         env.storage().persistent().set(&DataKey::Tanda(tanda_id.clone()), &tanda);
 
+        // Each owner posts their share of the slot's bond
+        for (owner, bond) in Self::split_by_share(&env, &member, Self::slot_bond(&tanda)).iter() {
+            Self::post_collateral(&env, &tanda, &owner, bond)?;
+        }
+        Self::extend_members_ttl(&env, &tanda);
+
         env.events().publish(
-            (Symbol::new(&env, "tanda_started"), caller),
-            tanda_id,
+            (Symbol::new(&env, "shared_slot_joined"), user),
+            (tanda_id, shares),
         );
-    }
 
-    // ==================== CORE OPERATIONS ====================
+        Self::start_if_full(&env, &mut tanda)
+    }
 
-    /// Deposit for current cycle
-    pub fn deposit(env: Env, user: Address, tanda_id: String) {
-        user.require_auth();
+    /// Start a new round of a completed tanda with the same settings (creator
+    /// only, no creation fee). Every slot of the previous round whose owners
+    /// were never expelled gets a reserved seat, which its owners take with
+    /// `confirm_seat`; the creator's are taken right away. The payout order
+    /// carries over: with a fixed one (join order, creator-assigned or
+    /// reverse) reserved seats are paid in the previous order, or rotated by
+    /// one. Returns the new tanda's ID.
+    pub fn renew_tanda(
+        env: Env,
+        creator: Address,
+        tanda_id: String,
+        order: RenewalOrder,
+    ) -> Result<String, TandaError> {
+        creator.require_auth();
 
-        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id);
+        let mut tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
 
-        if tanda.status != TandaStatus::Active {
-            panic!("tanda not active");
+        if tanda.creator != creator {
+            return Err(TandaError::NotCreator);
+        }
+        // Only completed tandas can be renewed, and only once
+        if tanda.status != TandaStatus::Completed {
+            return Err(TandaError::NotCompleted);
+        }
+        if tanda.renewed_as.is_some() {
+            return Err(TandaError::AlreadyRenewed);
         }
 
-        let mut members: Vec<Member> = Self::get_members_internal(&env, &tanda_id);
-
-        // Find member and check status
-        let mut member_idx: Option<u32> = None;
-        for (i, m) in members.iter().enumerate() {
-            if m.address == user {
+        // Anyone expelled from any of their slots loses their seat, including
+        // slots expelled before receiving, which have left the payout order
+        let mut expelled: Map<Address, bool> = Map::new(&env);
+        for address in Self::get_member_list(&env, &tanda_id).iter() {
+            for m in Self::get_slots(&env, &tanda, &address)?.iter() {
                 if m.status == MemberStatus::Expelled {
-                    panic!("member was expelled");
-                }
-                if m.has_deposited {
-                    panic!("already deposited this cycle");
+                    for owner in Self::owners(&env, &m).iter() {
+                        expelled.set(owner, true);
+                    }
                 }
-                member_idx = Some(i as u32);
-                break;
+            }
+        }
+        let mut reservations = Vec::new(&env);
+        for id in Self::get_positions(&env, &tanda_id).iter() {
+            let m = Self::get_slot(&env, &tanda, &id)?;
+            let owners = Self::owners(&env, &m);
+            if owners.iter().all(|owner| !expelled.contains_key(owner)) {
+                // A shared slot whose holder was dropped goes to an owner still in it
+                reservations.push_back(Reservation {
+                    address: owners.get(0).unwrap(),
+                    shares: m.shares,
+                    commitment: m.commitment,
+                    slot: None,
+                });
+            }
+        }
+        if order == RenewalOrder::Rotate {
+            if let Some(first) = reservations.pop_front() {
+                reservations.push_back(first);
             }
         }
 
-        let idx = member_idx.expect("not a member");
-
-        // Get token and addresses
-        let eurc_token: Address = env.storage().instance()
-            .get(&DataKey::EurcToken)
-            .expect("not initialized");
-        let commission_addr: Address = env.storage().instance()
-            .get(&DataKey::CommissionAddress)
-            .expect("not initialized");
-        let commission_bps: u32 = env.storage().instance()
-            .get(&DataKey::CommissionBps)
-            .unwrap_or(50);
-
-        // Calculate amounts
-        let commission = (tanda.amount * commission_bps as i128) / 10000;
-        let token = TokenClient::new(&env, &eurc_token);
-
-        // Transfer deposit to contract
-        token.transfer(&user, &env.current_contract_address(), &tanda.amount);
+        // Same settings, and no forming deadline
+        let id = Self::next_tanda_id(&env);
+        let now = env.ledger().timestamp();
+        let mut next = Tanda {
+            id: id.clone(),
+            status: TandaStatus::Forming,
+            current_cycle: 0,
+            total_cycles: 0,
+            active_members: 0,
+            created_at: now,
+            started_at: 0,
+            last_payout_at: 0,
+            forming_deadline: 0,
+            renewed_from: Some(tanda_id.clone()),
+            renewed_as: None,
+            ..tanda.clone()
+        };
+        env.storage().persistent().set(&DataKey::Tanda(id.clone()), &next);
+        env.storage().persistent().set(&DataKey::SafetyFund(id.clone()), &0i128);
+        env.storage().persistent().set(&DataKey::Reservations(id.clone()), &reservations);
+        if next.invite_only {
+            let codes = Self::get_invite_codes_internal(&env, &tanda_id);
+            env.storage().persistent().set(&DataKey::InviteCodes(id.clone()), &codes);
+        }
 
-        // Transfer commission
-        if commission > 0 {
-            token.transfer(&user, &commission_addr, &commission);
+        if Self::confirm_reservations(&env, &mut next, &creator)? == 0 {
+            return Err(TandaError::CreatorHasNoSeat);
         }
 
-        // Mark as deposited
-        let mut member = members.get(idx).unwrap();
-        member.has_deposited = true;
-        members.set(idx, member);
-        env.storage().persistent().set(&DataKey::Members(tanda_id.clone()), &members);
+        tanda.renewed_as = Some(id.clone());
+        env.storage().persistent().set(&DataKey::Tanda(tanda_id.clone()), &tanda);
+        Self::extend_tanda_ttl(&env, &tanda);
 
         env.events().publish(
-            (Symbol::new(&env, "deposit_made"), user),
-            (tanda_id, tanda.current_cycle),
+            (Symbol::new(&env, "tanda_renewed"), creator),
+            (tanda_id, id.clone(), reservations.len()),
         );
+
+        Self::start_if_full(&env, &mut next)?;
+        Ok(id)
     }
 
-    /// Trigger payout to current beneficiary (anyone can call)
-    /// Succeeds only if all active members have deposited
-    pub fn trigger_payout(env: Env, tanda_id: String) {
-        let mut tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id);
+    /// Take up the seats reserved for the caller in a renewed tanda, all in
+    /// one call. Bonds are posted as on joining.
+    pub fn confirm_seat(env: Env, user: Address, tanda_id: String) -> Result<(), TandaError> {
+        user.require_auth();
 
-        if tanda.status != TandaStatus::Active {
-            panic!("tanda not active");
+        let mut tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+
+        if tanda.status != TandaStatus::Forming {
+            return Err(TandaError::NotForming);
+        }
+        if Self::confirm_reservations(&env, &mut tanda, &user)? == 0 {
+            return Err(TandaError::NoReservedSeat);
         }
 
-        let mut members: Vec<Member> = Self::get_members_internal(&env, &tanda_id);
+        Self::start_if_full(&env, &mut tanda)
+    }
 
-        // Check all non-expelled members have deposited
-        let mut all_deposited = true;
-        let mut active_count: u32 = 0;
-        let mut beneficiary: Option<Address> = None;
-        let beneficiary_position = tanda.current_cycle - 1;
+    /// Register another invite code (creator only, while forming). A tanda
+    /// with codes is invite-only from then on.
+    pub fn add_invite_code(
+        env: Env,
+        creator: Address,
+        tanda_id: String,
+        code: InviteCode,
+    ) -> Result<(), TandaError> {
+        creator.require_auth();
 
-        for m in members.iter() {
-            if m.status != MemberStatus::Expelled {
-                active_count += 1;
-                if !m.has_deposited {
-                    all_deposited = false;
-                }
-                // Find beneficiary by position
-                if m.position == beneficiary_position && m.status == MemberStatus::Active {
-                    beneficiary = Some(m.address.clone());
-                }
-            }
-        }
+        let mut tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
 
-        if !all_deposited {
-            panic!("not all members have deposited");
+        if tanda.creator != creator {
+            return Err(TandaError::NotCreator);
+        }
+        if tanda.status != TandaStatus::Forming {
+            return Err(TandaError::NotForming);
         }
 
-        let recipient = beneficiary.expect("beneficiary not found");
+        let mut codes = Self::get_invite_codes_internal(&env, &tanda_id);
+        if codes.len() >= MAX_INVITE_CODES {
+            return Err(TandaError::TooManyInviteCodes);
+        }
+        codes.set(code.hash.clone(), code);
+        env.storage().persistent().set(&DataKey::InviteCodes(tanda_id.clone()), &codes);
 
-        // Calculate and transfer payout
-        let payout = tanda.amount * active_count as i128;
-        let eurc_token: Address = env.storage().instance()
-            .get(&DataKey::EurcToken)
-            .expect("not initialized");
+        tanda.invite_only = true;
+        env.storage().persistent().set(&DataKey::Tanda(tanda_id), &tanda);
+        Self::extend_tanda_ttl(&env, &tanda);
 
-        let token = TokenClient::new(&env, &eurc_token);
-        token.transfer(&env.current_contract_address(), &recipient, &payout);
+        Ok(())
+    }
 
-        // Update member statuses: mark recipient as Received, reset deposits
-        let mut new_members: Vec<Member> = Vec::new(&env);
-        for m in members.iter() {
-            let mut member = m.clone();
-            if member.address == recipient {
-                member.status = MemberStatus::Received;
-            }
-            member.has_deposited = false; // Reset for next cycle
-            new_members.push_back(member);
-        }
+    /// Revoke an invite code by its hash (creator only)
+    pub fn revoke_invite_code(
+        env: Env,
+        creator: Address,
+        tanda_id: String,
+        hash: BytesN<32>,
+    ) -> Result<(), TandaError> {
+        creator.require_auth();
 
-        // Advance cycle
-        let now = env.ledger().timestamp();
-        tanda.current_cycle += 1;
-        tanda.last_payout_at = now;
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
 
-        // Check if tanda is complete
-        if tanda.current_cycle > tanda.total_cycles {
-            tanda.status = TandaStatus::Completed;
+        if tanda.creator != creator {
+            return Err(TandaError::NotCreator);
         }
 
-        env.storage().persistent().set(&DataKey::Tanda(tanda_id.clone()), &tanda);
-        env.storage().persistent().set(&DataKey::Members(tanda_id.clone()), &new_members);
+        let mut codes = Self::get_invite_codes_internal(&env, &tanda_id);
+        if codes.remove(hash.clone()).is_none() {
+            return Err(TandaError::InvalidInviteCode);
+        }
+        env.storage().persistent().set(&DataKey::InviteCodes(tanda_id.clone()), &codes);
+        Self::extend_tanda_ttl(&env, &tanda);
 
         env.events().publish(
-            (Symbol::new(&env, "payout_sent"), recipient),
-            (tanda_id, payout),
+            (Symbol::new(&env, "invite_revoked"), creator),
+            (tanda_id, hash),
         );
+
+        Ok(())
     }
 
-    /// Expel a delinquent member (anyone can call)
-    /// Succeeds if member hasn't deposited and delinquency period has passed since last payout
-    pub fn expel_delinquent(env: Env, tanda_id: String, delinquent: Address) {
-        let mut tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id);
+    /// Approve a pending join request (creator only)
+    pub fn approve_member(
+        env: Env,
+        creator: Address,
+        tanda_id: String,
+        member: Address,
+    ) -> Result<(), TandaError> {
+        creator.require_auth();
 
-        if tanda.status != TandaStatus::Active {
-            panic!("tanda not active");
-        }
+        let mut tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
 
-        let now = env.ledger().timestamp();
-        let deadline = tanda.last_payout_at + (tanda.delinquency_days * SECONDS_PER_DAY);
-
-        if now < deadline {
-            panic!("delinquency period not passed");
-        }
-
-        let mut members: Vec<Member> = Self::get_members_internal(&env, &tanda_id);
-
-        // Find and validate delinquent
-        let mut found = false;
-        let mut had_received = false;
-        let mut new_members: Vec<Member> = Vec::new(&env);
-
-        for m in members.iter() {
-            let mut member = m.clone();
-            if member.address == delinquent {
-                if member.status == MemberStatus::Expelled {
-                    panic!("already expelled");
-                }
-                if member.has_deposited {
-                    panic!("member has deposited");
-                }
-                had_received = member.status == MemberStatus::Received;
-                member.status = MemberStatus::Expelled;
-                found = true;
-            }
-            new_members.push_back(member);
+        if tanda.creator != creator {
+            return Err(TandaError::NotCreator);
         }
-
-        if !found {
-            panic!("member not found");
+        if tanda.status != TandaStatus::Forming {
+            return Err(TandaError::NotForming);
         }
-
-        // If member hadn't received yet, reduce total cycles
-        if !had_received {
-            tanda.total_cycles -= 1;
+        if Self::seats_taken(&env, &tanda) >= tanda.max_members {
+            return Err(TandaError::TandaFull);
         }
 
-        // Reorder positions for remaining active members
-        let mut new_position: u32 = 0;
-        let mut reordered_members: Vec<Member> = Vec::new(&env);
-        for m in new_members.iter() {
-            let mut member = m.clone();
-            if member.status != MemberStatus::Expelled {
-                member.position = new_position;
-                new_position += 1;
-            }
-            reordered_members.push_back(member);
+        let request = Self::take_join_request(&env, &tanda_id, &member)?;
+        if request.expires_at <= env.ledger().timestamp() {
+            return Err(TandaError::RequestExpired);
         }
 
+        Self::add_slot(&env, &tanda, &member, 0, Map::new(&env));
+        tanda.active_members += 1;
         env.storage().persistent().set(&DataKey::Tanda(tanda_id.clone()), &tanda);
-        env.storage().persistent().set(&DataKey::Members(tanda_id.clone()), &reordered_members);
+
+        // The bond escrowed with the request becomes the member's collateral
+        if request.bond > 0 {
+            Self::hold_collateral(&env, &tanda_id, &member, request.bond);
+        }
+        Self::prune_join_requests(&env, &tanda_id)?;
+        Self::extend_tanda_ttl(&env, &tanda);
 
         env.events().publish(
-            (Symbol::new(&env, "member_expelled"), delinquent),
+            (Symbol::new(&env, "member_joined"), member),
             tanda_id,
         );
+
+        Self::start_if_full(&env, &mut tanda)
     }
 
-    /// Advance the tanda - single action that:
-    /// 1. Expels all delinquent members (if delinquency period passed without deposit)
-    /// 2. Triggers payout to beneficiary (if all remaining members deposited)
-    /// Anyone can call this function
-    pub fn advance(env: Env, tanda_id: String) -> bool {
-        let mut tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id);
+    /// Reject a pending join request and return its bond (creator only)
+    pub fn reject_member(
+        env: Env,
+        creator: Address,
+        tanda_id: String,
+        member: Address,
+    ) -> Result<(), TandaError> {
+        creator.require_auth();
 
-        if tanda.status != TandaStatus::Active {
-            panic!("tanda not active");
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+
+        if tanda.creator != creator {
+            return Err(TandaError::NotCreator);
         }
 
-        let now = env.ledger().timestamp();
-        let deadline = tanda.last_payout_at + (tanda.delinquency_days * SECONDS_PER_DAY);
-        let deadline_passed = now >= deadline;
+        let request = Self::take_join_request(&env, &tanda_id, &member)?;
+        Self::release_request_bond(&env, &request)?;
+        Self::extend_tanda_ttl(&env, &tanda);
 
-        let mut members: Vec<Member> = Self::get_members_internal(&env, &tanda_id);
-        let mut expelled_any = false;
+        env.events().publish(
+            (Symbol::new(&env, "request_rejected"), member),
+            tanda_id,
+        );
 
-        // Step 1: Expel delinquents if deadline passed
-        if deadline_passed {
-            let mut new_members: Vec<Member> = Vec::new(&env);
+        Ok(())
+    }
 
-            for m in members.iter() {
-                let mut member = m.clone();
-                if member.status != MemberStatus::Expelled && !member.has_deposited {
-                    // Expel this delinquent
-                    let had_received = member.status == MemberStatus::Received;
-                    member.status = MemberStatus::Expelled;
-                    expelled_any = true;
+    /// Set what the caller commits per cycle on each of their slots, in a
+    /// weighted tanda that hasn't started (the other owners of a shared slot
+    /// sign too). Commission, safety fund share and late fees follow the
+    /// commitment; bonds stay a multiple of `amount`.
+    pub fn set_commitment(
+        env: Env,
+        user: Address,
+        tanda_id: String,
+        commitment: i128,
+    ) -> Result<(), TandaError> {
+        user.require_auth();
 
-                    // Reduce cycles if they hadn't received yet
-                    if !had_received {
-                        tanda.total_cycles -= 1;
-                    }
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
 
-                    env.events().publish(
-                        (Symbol::new(&env, "member_expelled"), member.address.clone()),
-                        tanda_id.clone(),
-                    );
-                }
-                new_members.push_back(member);
-            }
-
-            if expelled_any {
-                // Reorder positions
-                let mut new_position: u32 = 0;
-                let mut reordered: Vec<Member> = Vec::new(&env);
-                for m in new_members.iter() {
-                    let mut member = m.clone();
-                    if member.status != MemberStatus::Expelled {
-                        member.position = new_position;
-                        new_position += 1;
-                    }
-                    reordered.push_back(member);
-                }
-                members = reordered;
-            }
+        if tanda.status != TandaStatus::Forming {
+            return Err(TandaError::NotForming);
+        }
+        if tanda.commitment_mode == CommitmentMode::Equal || commitment <= 0 {
+            return Err(TandaError::InvalidAmount);
         }
 
-        // Step 2: Check if all remaining members have deposited
-        let mut all_deposited = true;
-        let mut active_count: u32 = 0;
-        let mut beneficiary: Option<Address> = None;
-        let beneficiary_position = tanda.current_cycle - 1;
-
-        for m in members.iter() {
-            if m.status != MemberStatus::Expelled {
-                active_count += 1;
-                if !m.has_deposited {
-                    all_deposited = false;
-                }
-                if m.position == beneficiary_position && m.status == MemberStatus::Active {
-                    beneficiary = Some(m.address.clone());
+        for mut member in Self::get_slots(&env, &tanda, &user)?.iter() {
+            for owner in Self::owners(&env, &member).iter() {
+                if owner != user {
+                    owner.require_auth();
                 }
             }
+            member.commitment = commitment;
+            Self::save_member(&env, &tanda_id, &member);
         }
+        Self::extend_tanda_ttl(&env, &tanda);
 
-        // Check if tanda should complete (no active members left or only 1)
-        if active_count <= 1 {
-            tanda.status = TandaStatus::Completed;
-            env.storage().persistent().set(&DataKey::Tanda(tanda_id.clone()), &tanda);
-            env.storage().persistent().set(&DataKey::Members(tanda_id.clone()), &members);
-            return expelled_any;
-        }
+        env.events().publish(
+            (Symbol::new(&env, "commitment_set"), user),
+            (tanda_id, commitment),
+        );
 
-        // Step 3: Trigger payout if all deposited
-        if all_deposited {
-            let recipient = beneficiary.expect("beneficiary not found");
+        Ok(())
+    }
 
-            // Transfer payout
-            let payout = tanda.amount * active_count as i128;
-            let eurc_token: Address = env.storage().instance()
-                .get(&DataKey::EurcToken)
-                .expect("not initialized");
+    /// Leave a tanda before it starts, giving up every slot held. Everyone
+    /// behind the leaver moves up; a shared slot is given up by all of its
+    /// owners, who all sign. A leaving creator hands the role to the next member in the
+    /// payout order, or cancels the tanda if nobody else has joined. Someone
+    /// with a pending join request withdraws it instead.
+    pub fn leave_tanda(env: Env, user: Address, tanda_id: String) -> Result<(), TandaError> {
+        user.require_auth();
 
-            let token = TokenClient::new(&env, &eurc_token);
-            token.transfer(&env.current_contract_address(), &recipient, &payout);
+        let mut tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
 
-            // Update members: mark recipient as Received, reset deposits
-            let mut updated_members: Vec<Member> = Vec::new(&env);
-            for m in members.iter() {
-                let mut member = m.clone();
-                if member.address == recipient {
-                    member.status = MemberStatus::Received;
-                }
-                member.has_deposited = false;
-                updated_members.push_back(member);
+        if tanda.status != TandaStatus::Forming {
+            return Err(TandaError::NotForming);
+        }
+
+        let slots = match Self::get_slots(&env, &tanda, &user) {
+            Ok(slots) => slots,
+            Err(TandaError::NotMember) => {
+                let request = Self::take_join_request(&env, &tanda_id, &user)
+                    .map_err(|_| TandaError::NotMember)?;
+                Self::release_request_bond(&env, &request)?;
+                Self::extend_tanda_ttl(&env, &tanda);
+                return Ok(());
             }
-            members = updated_members;
+            Err(e) => return Err(e),
+        };
 
-            // Advance cycle
-            tanda.current_cycle += 1;
-            tanda.last_payout_at = now;
+        // Drop the slots from the payout order, their records and the join list
+        let holder = slots.get(0).unwrap().address;
+        let leavers = Self::owners(&env, &slots.get(0).unwrap());
+        for owner in leavers.iter() {
+            if owner != user {
+                owner.require_auth();
+            }
+        }
+        let mut positions = Vec::new(&env);
+        for id in Self::get_positions(&env, &tanda_id).iter() {
+            if id.address != holder {
+                positions.push_back(id);
+            }
+        }
+        for member in slots.iter() {
+            env.storage().persistent().remove(&Self::member_key(&tanda_id, &holder, member.slot));
+        }
+        Self::set_positions(&env, &tanda, &positions)?;
 
-            if tanda.current_cycle > tanda.total_cycles {
-                tanda.status = TandaStatus::Completed;
+        let mut list = Self::get_member_list(&env, &tanda_id);
+        for leaver in leavers.iter() {
+            if let Some(i) = list.first_index_of(&leaver) {
+                list.remove(i);
             }
+        }
+        env.storage().persistent().set(&DataKey::MemberList(tanda_id.clone()), &list);
+        tanda.active_members -= slots.len();
+
+        for leaver in leavers.iter() {
+            env.storage().persistent().remove(&DataKey::CoOwner(tanda_id.clone(), leaver.clone()));
+            Self::return_collateral(&env, &tanda_id, &leaver, i128::MAX)?;
 
             env.events().publish(
-                (Symbol::new(&env, "payout_sent"), recipient),
-                (tanda_id.clone(), payout),
+                (Symbol::new(&env, "member_left"), leaver),
+                tanda_id.clone(),
             );
         }
 
-        // Save state
-        env.storage().persistent().set(&DataKey::Tanda(tanda_id.clone()), &tanda);
-        env.storage().persistent().set(&DataKey::Members(tanda_id.clone()), &members);
+        if leavers.contains(&tanda.creator) {
+            match positions.first() {
+                Some(next) => {
+                    tanda.creator = next.address.clone();
+                    env.events().publish(
+                        (Symbol::new(&env, "creator_changed"), next.address),
+                        tanda_id.clone(),
+                    );
+                }
+                None => {
+                    let commission_addr = Self::get_commission_address(&env)?;
+                    Self::cancel_internal(&env, &mut tanda, &commission_addr)?;
+                }
+            }
+        }
 
-        expelled_any || all_deposited
+        env.storage().persistent().set(&DataKey::Tanda(tanda_id), &tanda);
+        Self::extend_tanda_ttl(&env, &tanda);
+
+        Ok(())
     }
 
-    /// Cancel tanda and refund deposits (requires >50% vote)
-    /// For simplicity, only creator can cancel while in Forming status
-    pub fn cancel_tanda(env: Env, caller: Address, tanda_id: String) {
-        caller.require_auth();
+    /// Set the payout order of a `CreatorAssigned` tanda before it starts
+    /// (creator only). `order` must list every current member once per slot
+    /// they hold (their first slot at their first appearance); anyone joining
+    /// afterwards is paid after them.
+    pub fn assign_payout_order(
+        env: Env,
+        creator: Address,
+        tanda_id: String,
+        order: Vec<Address>,
+    ) -> Result<(), TandaError> {
+        creator.require_auth();
 
-        let mut tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id);
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
 
-        // Only creator can cancel, only in Forming status
-        if tanda.creator != caller {
-            panic!("only creator can cancel");
+        if tanda.creator != creator {
+            return Err(TandaError::NotCreator);
         }
         if tanda.status != TandaStatus::Forming {
-            panic!("can only cancel while forming");
+            return Err(TandaError::NotForming);
+        }
+        if tanda.payout_order != PayoutOrder::CreatorAssigned {
+            return Err(TandaError::OrderNotAssignable);
         }
 
-        tanda.status = TandaStatus::Cancelled;
-        env.storage().persistent().set(&DataKey::Tanda(tanda_id.clone()), &tanda);
+        // Same length, only members, never more often than their slots: a permutation
+        if order.len() != Self::get_positions(&env, &tanda_id).len() {
+            return Err(TandaError::InvalidPayoutOrder);
+        }
+        let mut seen: Map<Address, u32> = Map::new(&env);
+        let mut positions = Vec::new(&env);
+        for address in order.iter() {
+            let slot = seen.get(address.clone()).unwrap_or(0);
+            if slot >= Self::slot_count(&env, &tanda_id, &address) {
+                return Err(TandaError::InvalidPayoutOrder);
+            }
+            seen.set(address.clone(), slot + 1);
+            positions.push_back(SlotId { address, slot });
+        }
+
+        Self::set_positions(&env, &tanda, &positions)?;
+        Self::extend_tanda_ttl(&env, &tanda);
 
         env.events().publish(
-            (Symbol::new(&env, "tanda_cancelled"), caller),
-            tanda_id,
+            (Symbol::new(&env, "payout_order_assigned"), creator),
+            (tanda_id, order),
         );
+
+        Ok(())
     }
 
-    // ==================== VIEW FUNCTIONS ====================
+    /// Start the tanda (creator only, requires at least 2 members)
+    pub fn start_tanda(env: Env, caller: Address, tanda_id: String) -> Result<(), TandaError> {
+        caller.require_auth();
 
-    /// Get tanda details
-    pub fn get_tanda(env: Env, tanda_id: String) -> Tanda {
-        Self::get_tanda_internal(&env, &tanda_id)
-    }
+        let mut tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+
+        if tanda.creator != caller {
+            return Err(TandaError::NotCreator);
+        }
+        if tanda.status != TandaStatus::Forming {
+            return Err(TandaError::NotForming);
+        }
 
-    /// Get tanda members
-    pub fn get_members(env: Env, tanda_id: String) -> Vec<Member> {
-        Self::get_members_internal(&env, &tanda_id)
+        Self::start_internal(&env, &mut tanda)
     }
 
-    /// Check if all members have deposited for current cycle
-    pub fn all_deposited(env: Env, tanda_id: String) -> bool {
-        let members: Vec<Member> = Self::get_members_internal(&env, &tanda_id);
+    /// Resolve a tanda still forming after its deadline (anyone can call).
+    /// Depending on the policy chosen at creation it starts with whoever has
+    /// joined, or is cancelled and the creation fee refunded to the creator.
+    pub fn expire_forming(env: Env, tanda_id: String) -> Result<TandaStatus, TandaError> {
+        let mut tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
 
-        for m in members.iter() {
-            if m.status != MemberStatus::Expelled && !m.has_deposited {
-                return false;
-            }
+        if tanda.status != TandaStatus::Forming {
+            return Err(TandaError::NotForming);
+        }
+        if tanda.forming_deadline == 0 {
+            return Err(TandaError::NoFormingDeadline);
+        }
+        if env.ledger().timestamp() < tanda.forming_deadline {
+            return Err(TandaError::FormingNotExpired);
+        }
+
+        if tanda.forming_expiry == FormingExpiry::StartIfReady && tanda.active_members >= MIN_MEMBERS {
+            Self::start_internal(&env, &mut tanda)?;
+        } else {
+            let creator = tanda.creator.clone();
+            Self::cancel_internal(&env, &mut tanda, &creator)?;
         }
-        true
+
+        Ok(tanda.status)
     }
 
-    /// Get current beneficiary (who will receive next payout)
-    pub fn get_beneficiary(env: Env, tanda_id: String) -> Address {
-        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id);
-        let members: Vec<Member> = Self::get_members_internal(&env, &tanda_id);
+    // ==================== CORE OPERATIONS ====================
+
+    /// Deposit for current cycle (whatever is left of it after installments)
+    /// on every slot the caller holds, in a single transfer. A co-owner of a
+    /// shared slot pays their own share.
+    pub fn deposit(env: Env, user: Address, tanda_id: String) -> Result<(), TandaError> {
+        user.require_auth();
 
-        let position = tanda.current_cycle - 1;
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+        let slots = Self::get_slots(&env, &tanda, &user)?;
 
-        for m in members.iter() {
-            if m.position == position && m.status == MemberStatus::Active {
-                return m.address.clone();
+        let mut payments = Vec::new(&env);
+        for member in slots.iter() {
+            let owed = Self::owed_by(&env, &tanda, &member, &user).unwrap_or(0);
+            if member.status != MemberStatus::Expelled && !member.has_deposited && owed > 0 {
+                payments.push_back((member, owed));
             }
         }
+        // Nothing left to pay: let the first slot report why
+        if payments.is_empty() {
+            let member = slots.get(0).unwrap();
+            let owed = Self::owed_by(&env, &tanda, &member, &user).unwrap_or(0);
+            payments.push_back((member, owed));
+        }
+
+        Self::deposit_internal(&env, &tanda, &user, payments)
+    }
+
+    /// Deposit for the current cycle on one of the caller's slots
+    pub fn deposit_slot(env: Env, user: Address, tanda_id: String, slot: u32) -> Result<(), TandaError> {
+        user.require_auth();
+
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+        let member = Self::get_slot(&env, &tanda, &SlotId { address: user.clone(), slot })?;
+        let owed = Self::owed_by(&env, &tanda, &member, &user).unwrap_or(0);
+
+        Self::deposit_internal(&env, &tanda, &user, Vec::from_array(&env, [(member, owed)]))
+    }
+
+    /// Pay part of the current cycle's contribution. The member counts as
+    /// deposited once the installments add up to the tanda amount; commission
+    /// and safety fund shares are charged on each piece. Members with several
+    /// slots pay toward their first slot still open, co-owners toward their share.
+    pub fn deposit_partial(
+        env: Env,
+        user: Address,
+        tanda_id: String,
+        amount: i128,
+    ) -> Result<(), TandaError> {
+        user.require_auth();
 
-        panic!("beneficiary not found");
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+        let slots = Self::get_slots(&env, &tanda, &user)?;
+        let member = slots.iter()
+            .find(|m| {
+                let owed = Self::owed_by(&env, &tanda, m, &user).unwrap_or(0);
+                m.status != MemberStatus::Expelled && !m.has_deposited && owed > 0
+            })
+            .unwrap_or(slots.get(0).unwrap());
+
+        Self::deposit_internal(&env, &tanda, &user, Vec::from_array(&env, [(member, amount)]))
     }
 
-    /// Check if a member can be expelled (delinquency period passed without deposit)
-    pub fn can_expel(env: Env, tanda_id: String, member: Address) -> bool {
-        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id);
+    /// Pay for the next `cycles` cycles up front (amount, commission and any
+    /// safety fund share). Each new cycle is then marked deposited
+    /// automatically, starting with the current one if nothing was paid for it
+    /// yet. Unused cycles are refunded at completion or expulsion.
+    pub fn prepay(env: Env, user: Address, tanda_id: String, cycles: u32) -> Result<(), TandaError> {
+        user.require_auth();
+
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
 
         if tanda.status != TandaStatus::Active {
-            return false;
+            return Err(TandaError::NotActive);
         }
 
-        let now = env.ledger().timestamp();
-        let deadline = tanda.last_payout_at + (tanda.delinquency_days * SECONDS_PER_DAY);
+        let slots = Self::get_slots(&env, &tanda, &user)?;
+        let member = slots.get(0).unwrap();
+        if member.status == MemberStatus::Expelled {
+            return Err(TandaError::MemberExpelled);
+        }
+        // Prepaid cycles are kept per address, so they cover a single whole
+        // slot, and need the same dues every cycle
+        if slots.len() > 1 || !member.shares.is_empty() {
+            return Err(TandaError::InvalidPrepayment);
+        }
+        if tanda.commitment_mode == CommitmentMode::Proportional {
+            return Err(TandaError::InvalidPrepayment);
+        }
 
-        if now < deadline {
-            return false;
+        // Only cycles still to be paid for can be prepaid
+        let mut prepaid = Self::get_prepaid_internal(&env, &tanda_id);
+        let mut prepayment = prepaid.get(user.clone())
+            .unwrap_or(Prepayment { cycles: 0, commission: 0 });
+        let mut unpaid = tanda.total_cycles - tanda.current_cycle + 1;
+        if member.paid_this_cycle > 0 {
+            unpaid -= 1;
         }
+        if cycles == 0 || prepayment.cycles + cycles > unpaid {
+            return Err(TandaError::InvalidPrepayment);
+        }
+
+        let eurc_token: Address = env.storage().instance()
+            .get(&DataKey::EurcToken)
+            .ok_or(TandaError::NotInitialized)?;
+        let commission_bps: u32 = env.storage().instance()
+            .get(&DataKey::CommissionBps)
+            .unwrap_or(50);
+
+        let commission = (member.commitment * commission_bps as i128) / 10000 * cycles as i128;
+        let held = Self::prepaid_cycle_cost(&tanda, member.commitment) * cycles as i128 + commission;
+        let token = TokenClient::new(&env, &eurc_token);
+        token.transfer(&user, &env.current_contract_address(), &held);
+        Self::adjust_total_escrow(&env, held);
 
-        let members: Vec<Member> = Self::get_members_internal(&env, &tanda_id);
+        prepayment.cycles += cycles;
+        prepayment.commission += commission;
+        prepaid.set(user.clone(), prepayment);
+        env.storage().persistent().set(&DataKey::Prepaid(tanda_id.clone()), &prepaid);
 
-        for m in members.iter() {
-            if m.address == member {
-                return m.status != MemberStatus::Expelled && !m.has_deposited;
-            }
+        env.events().publish(
+            (Symbol::new(&env, "cycles_prepaid"), user.clone()),
+            (tanda_id.clone(), cycles),
+        );
+
+        if member.paid_this_cycle == 0 {
+            Self::apply_prepayment(&env, &tanda, &user)?;
         }
+        Self::extend_tanda_ttl(&env, &tanda);
 
-        false
+        Ok(())
     }
 
-    /// Get seconds until delinquency deadline
-    pub fn time_to_deadline(env: Env, tanda_id: String) -> u64 {
-        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id);
-        let now = env.ledger().timestamp();
-        let deadline = tanda.last_payout_at + (tanda.delinquency_days * SECONDS_PER_DAY);
+    /// Trigger payout to current beneficiary (anyone can call)
+    /// Succeeds only if all active members have deposited
+    pub fn trigger_payout(env: Env, tanda_id: String) -> Result<(), TandaError> {
+        let mut tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
 
-        if now >= deadline {
-            return 0;
+        if tanda.status != TandaStatus::Active {
+            return Err(TandaError::NotActive);
         }
 
-        deadline - now
+        if !Self::all_deposited_internal(&env, &tanda) {
+            return Err(TandaError::NotAllDeposited);
+        }
+        if !Self::bids_revealed(&env, &tanda) {
+            return Err(TandaError::RevealPending);
+        }
+        if !Self::payout_due(&env, &tanda) {
+            return Err(TandaError::PayoutNotDue);
+        }
+
+        Self::pay_beneficiary(&env, &tanda_id, &mut tanda)?;
+
+        env.storage().persistent().set(&DataKey::Tanda(tanda_id), &tanda);
+        Self::extend_members_ttl(&env, &tanda);
+
+        Ok(())
     }
 
-    /// Get status for "Advance" button - what will happen if called
-    /// Returns: (can_advance, will_expel_count, will_payout, beneficiary_if_payout)
-    pub fn get_advance_status(env: Env, tanda_id: String) -> (bool, u32, bool, Option<Address>) {
-        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id);
+    /// Expel a delinquent member (anyone can call)
+    /// Succeeds if member hasn't deposited and the delinquency deadline (plus
+    /// any grace window) has passed. Only the member's slots left unpaid are
+    /// expelled; slots they paid for stay in the rotation.
+    pub fn expel_delinquent(env: Env, tanda_id: String, delinquent: Address) -> Result<(), TandaError> {
+        let mut tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
 
         if tanda.status != TandaStatus::Active {
-            return (false, 0, false, None);
+            return Err(TandaError::NotActive);
         }
 
         let now = env.ledger().timestamp();
-        let deadline = tanda.last_payout_at + (tanda.delinquency_days * SECONDS_PER_DAY);
-        let deadline_passed = now >= deadline;
-
-        let members: Vec<Member> = Self::get_members_internal(&env, &tanda_id);
+        let deadline = Self::expulsion_deadline(&tanda);
 
-        // Count who would be expelled
-        let mut expel_count: u32 = 0;
-        let mut remaining_deposited: u32 = 0;
-        let mut remaining_total: u32 = 0;
-        let mut beneficiary: Option<Address> = None;
-        let beneficiary_position = tanda.current_cycle - 1;
+        if now < deadline {
+            return Err(TandaError::DelinquencyNotPassed);
+        }
 
-        for m in members.iter() {
-            if m.status == MemberStatus::Expelled {
+        // Validate delinquent (each slot is reloaded, as expulsions move positions)
+        let slots = Self::get_slots(&env, &tanda, &delinquent)?;
+        let mut expelled_any = false;
+        let mut deposited_any = false;
+        for slot in slots.iter() {
+            let id = SlotId { address: slot.address, slot: slot.slot };
+            let mut member = Self::get_slot(&env, &tanda, &id)?;
+            if member.status == MemberStatus::Expelled {
+                continue;
+            }
+            if member.has_deposited {
+                deposited_any = true;
                 continue;
             }
+            Self::expel_member(&env, &tanda_id, &mut tanda, &mut member)?;
+            expelled_any = true;
+        }
+        if !expelled_any {
+            return Err(if deposited_any {
+                TandaError::MemberHasDeposited
+            } else {
+                TandaError::AlreadyExpelled
+            });
+        }
+        Self::close_if_exhausted(&env, &tanda_id, &mut tanda)?;
 
-            let would_be_expelled = deadline_passed && !m.has_deposited;
+        env.storage().persistent().set(&DataKey::Tanda(tanda_id), &tanda);
+        Self::extend_tanda_ttl(&env, &tanda);
 
-            if would_be_expelled {
-                expel_count += 1;
-            } else {
-                remaining_total += 1;
-                if m.has_deposited {
-                    remaining_deposited += 1;
-                }
-                // Find beneficiary among non-expelled
-                if m.position == beneficiary_position && m.status == MemberStatus::Active {
-                    beneficiary = Some(m.address.clone());
+        Ok(())
+    }
+
+    /// Advance the tanda - single action that:
+    /// 1. Expels all delinquent members (if delinquency period passed without deposit)
+    /// 2. Triggers payout to beneficiary (if all remaining members deposited)
+    ///
+    /// Anyone can call this function
+    pub fn advance(env: Env, tanda_id: String) -> Result<bool, TandaError> {
+        let mut tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+
+        if tanda.status != TandaStatus::Active {
+            return Err(TandaError::NotActive);
+        }
+
+        let now = env.ledger().timestamp();
+        let deadline = Self::expulsion_deadline(&tanda);
+        let deadline_passed = now >= deadline;
+
+        let mut expelled_any = false;
+
+        // Step 1: Expel delinquents if deadline passed (members are only
+        // loaded one by one when someone is actually missing)
+        if deadline_passed && !Self::all_deposited_internal(&env, &tanda) {
+            for id in Self::get_positions(&env, &tanda_id).iter() {
+                let mut m = Self::get_slot(&env, &tanda, &id)?;
+                if m.status != MemberStatus::Expelled && !m.has_deposited {
+                    Self::expel_member(&env, &tanda_id, &mut tanda, &mut m)?;
+                    expelled_any = true;
                 }
             }
         }
 
-        // Would payout happen after expulsions?
-        let will_payout = remaining_total > 1 && remaining_deposited == remaining_total;
+        // Step 2: Close the tanda if nobody is left to pay (refunds this cycle's deposits)
+        if Self::close_if_exhausted(&env, &tanda_id, &mut tanda)? {
+            env.storage().persistent().set(&DataKey::Tanda(tanda_id), &tanda);
+            Self::extend_tanda_ttl(&env, &tanda);
+            return Ok(expelled_any);
+        }
 
-        // Can advance if something would happen
-        let can_advance = expel_count > 0 || will_payout;
+        // Step 3: Trigger payout if all remaining members deposited (and, on a
+        // fixed schedule, the cycle is due or everyone agreed to pay out early)
+        let paid_out = Self::all_deposited_internal(&env, &tanda) && Self::payout_due(&env, &tanda);
+        if paid_out {
+            Self::pay_beneficiary(&env, &tanda_id, &mut tanda)?;
+        }
+
+        // Save state
+        env.storage().persistent().set(&DataKey::Tanda(tanda_id), &tanda);
+        if paid_out {
+            Self::extend_members_ttl(&env, &tanda);
+        } else {
+            Self::extend_tanda_ttl(&env, &tanda);
+        }
 
-        (can_advance, expel_count, will_payout, beneficiary)
+        Ok(expelled_any || paid_out)
     }
 
-    // ==================== INTERNAL HELPERS ====================
+    /// Agree to pay the current cycle out before its due date (fixed schedule
+    /// only). Once every member has agreed, the payout can happen early.
+    pub fn consent_early_payout(env: Env, user: Address, tanda_id: String) -> Result<(), TandaError> {
+        user.require_auth();
 
-    fn get_tanda_internal(env: &Env, tanda_id: &String) -> Tanda {
-        env.storage().persistent()
-            .get(&DataKey::Tanda(tanda_id.clone()))
-            .expect("tanda not found")
-    }
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
 
-    fn get_members_internal(env: &Env, tanda_id: &String) -> Vec<Member> {
-        env.storage().persistent()
-            .get(&DataKey::Members(tanda_id.clone()))
-            .unwrap_or(Vec::new(env))
-    }
+        if tanda.status != TandaStatus::Active {
+            return Err(TandaError::NotActive);
+        }
+        if tanda.schedule != Schedule::Fixed {
+            return Err(TandaError::NotScheduled);
+        }
 
-    fn generate_id(env: &Env, count: u32) -> String {
-        // Simple numeric ID
-        let mut chars = [b'0'; 8];
-        let mut n = count;
-        for i in 0..8 {
-            chars[7 - i] = b'0' + (n % 10) as u8;
-            n /= 10;
+        let slots = Self::get_slots(&env, &tanda, &user)?;
+        if slots.iter().all(|m| m.status == MemberStatus::Expelled) {
+            return Err(TandaError::MemberExpelled);
         }
-        String::from_str(env, core::str::from_utf8(&chars).unwrap())
-    }
-}
 
-// ==================== TESTS ====================
+        let key = DataKey::EarlyConsents(tanda_id.clone(), tanda.current_cycle);
+        let mut consents: Vec<Address> = env.storage().persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        if consents.contains(&user) {
+            return Err(TandaError::AlreadyConsented);
+        }
+        consents.push_back(user.clone());
+        env.storage().persistent().set(&key, &consents);
+        Self::extend_tanda_ttl(&env, &tanda);
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use soroban_sdk::testutils::{Address as _, Ledger, LedgerInfo};
+        env.events().publish(
+            (Symbol::new(&env, "early_payout_consent"), user),
+            (tanda_id, tanda.current_cycle),
+        );
 
-    fn setup_env() -> (Env, Address, Address, Address) {
+        Ok(())
+    }
+
+    /// Cancel tanda and refund deposits (requires >50% vote)
+    /// For simplicity, only creator can cancel while in Forming status
+    pub fn cancel_tanda(env: Env, caller: Address, tanda_id: String) -> Result<(), TandaError> {
+        caller.require_auth();
+
+        let mut tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+
+        // Only creator can cancel, only in Forming status
+        if tanda.creator != caller {
+            return Err(TandaError::NotCreator);
+        }
+        if tanda.status != TandaStatus::Forming {
+            return Err(TandaError::NotForming);
+        }
+
+        // A creator who cancels doesn't get a held creation fee back
+        let commission_addr = Self::get_commission_address(&env)?;
+        Self::cancel_internal(&env, &mut tanda, &commission_addr)
+    }
+
+    /// Join the waitlist of an active tanda. The most a replacement can owe
+    /// (every cycle's amount plus any collateral) is escrowed now so the swap
+    /// can happen when someone is expelled; whatever isn't used is returned.
+    /// Private tandas don't take strangers off a waitlist.
+    pub fn join_waitlist(env: Env, user: Address, tanda_id: String) -> Result<(), TandaError> {
+        user.require_auth();
+
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+
+        if tanda.status != TandaStatus::Active {
+            return Err(TandaError::NotActive);
+        }
+        if tanda.invite_only || tanda.visibility == Visibility::ApprovalRequired {
+            return Err(TandaError::WaitlistUnavailable);
+        }
+        // What is escrowed assumes every slot pays `amount`
+        if tanda.commitment_mode != CommitmentMode::Equal {
+            return Err(TandaError::WaitlistUnavailable);
+        }
+        if Self::is_member(&env, &tanda_id, &user) {
+            return Err(TandaError::AlreadyMember);
+        }
+
+        let mut waitlist = Self::get_waitlist(env.clone(), tanda_id.clone());
+        if waitlist.iter().any(|e| e.address == user) {
+            return Err(TandaError::RequestPending);
+        }
+        if waitlist.len() >= tanda.max_members {
+            return Err(TandaError::TandaFull);
+        }
+
+        let eurc_token: Address = env.storage().instance()
+            .get(&DataKey::EurcToken)
+            .ok_or(TandaError::NotInitialized)?;
+        let commission_bps: u32 = env.storage().instance()
+            .get(&DataKey::CommissionBps)
+            .unwrap_or(50);
+
+        // The open cycle's deposit pays commission and the safety fund share
+        // like any other; commission is fixed now, as for prepaid cycles
+        let commission = (tanda.amount * commission_bps as i128) / 10000;
+        let held = tanda.amount * (tanda.total_cycles - 1 + tanda.collateral_multiple) as i128
+            + Self::prepaid_cycle_cost(&tanda, tanda.amount)
+            + commission;
+        let token = TokenClient::new(&env, &eurc_token);
+        token.transfer(&user, &env.current_contract_address(), &held);
+        Self::adjust_total_escrow(&env, held);
+
+        waitlist.push_back(WaitlistEntry {
+            address: user.clone(),
+            joined_at: env.ledger().timestamp(),
+            held,
+            commission,
+        });
+        env.storage().persistent().set(&DataKey::Waitlist(tanda_id.clone()), &waitlist);
+        Self::extend_tanda_ttl(&env, &tanda);
+
+        env.events().publish(
+            (Symbol::new(&env, "waitlist_joined"), user),
+            (tanda_id, held),
+        );
+
+        Ok(())
+    }
+
+    /// Leave a tanda's waitlist and get the escrowed amount back
+    pub fn leave_waitlist(env: Env, user: Address, tanda_id: String) -> Result<(), TandaError> {
+        user.require_auth();
+
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+
+        let mut waitlist = Self::get_waitlist(env.clone(), tanda_id.clone());
+        let index = waitlist.iter()
+            .position(|e| e.address == user)
+            .ok_or(TandaError::RequestNotFound)? as u32;
+        let entry = waitlist.get(index).unwrap();
+        waitlist.remove(index);
+        env.storage().persistent().set(&DataKey::Waitlist(tanda_id.clone()), &waitlist);
+
+        Self::transfer_out(&env, &user, entry.held)?;
+        Self::adjust_total_escrow(&env, -entry.held);
+        Self::extend_tanda_ttl(&env, &tanda);
+
+        env.events().publish(
+            (Symbol::new(&env, "waitlist_left"), user),
+            tanda_id,
+        );
+
+        Ok(())
+    }
+
+    /// Offer to exchange payout positions with another member. Both must still
+    /// be waiting for their payout; `to` completes the swap with `accept_swap`.
+    /// A new proposal replaces the proposer's previous one. Members holding
+    /// several slots trade the next one due to be paid.
+    pub fn propose_swap(
+        env: Env,
+        from: Address,
+        to: Address,
+        tanda_id: String,
+    ) -> Result<(), TandaError> {
+        from.require_auth();
+
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+        Self::check_swap(&env, &tanda, &from, &to)?;
+
+        let mut proposals = Self::get_swap_proposals(env.clone(), tanda_id.clone());
+        proposals.set(from.clone(), to.clone());
+        env.storage().persistent().set(&DataKey::SwapProposals(tanda_id.clone()), &proposals);
+        Self::extend_tanda_ttl(&env, &tanda);
+
+        env.events().publish(
+            (Symbol::new(&env, "swap_proposed"), from, to),
+            tanda_id,
+        );
+
+        Ok(())
+    }
+
+    /// Accept a swap `from` proposed, exchanging the two members' positions
+    pub fn accept_swap(
+        env: Env,
+        to: Address,
+        from: Address,
+        tanda_id: String,
+    ) -> Result<(), TandaError> {
+        to.require_auth();
+
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+
+        let mut proposals = Self::get_swap_proposals(env.clone(), tanda_id.clone());
+        if proposals.get(from.clone()) != Some(to.clone()) {
+            return Err(TandaError::SwapNotFound);
+        }
+
+        // Either side may have been paid or expelled since the proposal
+        let (a, b) = Self::check_swap(&env, &tanda, &from, &to)?;
+        Self::swap_positions(&env, &tanda, a.position, b.position)?;
+
+        proposals.remove(from.clone());
+        env.storage().persistent().set(&DataKey::SwapProposals(tanda_id.clone()), &proposals);
+        Self::drop_listings(&env, &tanda_id, &from, &to);
+        Self::extend_tanda_ttl(&env, &tanda);
+
+        env.events().publish(
+            (Symbol::new(&env, "positions_swapped"), from, to),
+            (tanda_id, b.position, a.position),
+        );
+
+        Ok(())
+    }
+
+    /// Put your payout position up for sale at an asking premium. The listing
+    /// lapses once the slot's cycle starts; listing again updates the premium.
+    pub fn list_position(
+        env: Env,
+        seller: Address,
+        tanda_id: String,
+        premium: i128,
+    ) -> Result<(), TandaError> {
+        seller.require_auth();
+
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+
+        if premium <= 0 {
+            return Err(TandaError::InvalidPremium);
+        }
+        let member = Self::check_tradable(&env, &tanda, &seller)?;
+        if !Self::listing_live(&tanda, &member) {
+            return Err(TandaError::ListingExpired);
+        }
+
+        let mut listings = Self::get_listings_internal(&env, &tanda_id);
+        listings.set(seller.clone(), premium);
+        env.storage().persistent().set(&DataKey::Listings(tanda_id.clone()), &listings);
+        Self::extend_tanda_ttl(&env, &tanda);
+
+        env.events().publish(
+            (Symbol::new(&env, "position_listed"), seller),
+            (tanda_id, member.position, premium),
+        );
+
+        Ok(())
+    }
+
+    /// Take your position off the market
+    pub fn cancel_listing(env: Env, seller: Address, tanda_id: String) -> Result<(), TandaError> {
+        seller.require_auth();
+
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+
+        let mut listings = Self::get_listings_internal(&env, &tanda_id);
+        if listings.remove(seller.clone()).is_none() {
+            return Err(TandaError::ListingNotFound);
+        }
+        env.storage().persistent().set(&DataKey::Listings(tanda_id.clone()), &listings);
+        Self::extend_tanda_ttl(&env, &tanda);
+
+        env.events().publish(
+            (Symbol::new(&env, "listing_cancelled"), seller),
+            tanda_id,
+        );
+
+        Ok(())
+    }
+
+    /// Buy a listed position: the premium goes to the seller and the two
+    /// members exchange positions in the same call
+    pub fn buy_position(
+        env: Env,
+        buyer: Address,
+        tanda_id: String,
+        seller: Address,
+    ) -> Result<(), TandaError> {
+        buyer.require_auth();
+
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+
+        let premium = Self::get_listings_internal(&env, &tanda_id)
+            .get(seller.clone())
+            .ok_or(TandaError::ListingNotFound)?;
+        let (sold, bought) = Self::check_swap(&env, &tanda, &seller, &buyer)?;
+        if !Self::listing_live(&tanda, &sold) {
+            return Err(TandaError::ListingExpired);
+        }
+
+        let eurc_token: Address = env.storage().instance()
+            .get(&DataKey::EurcToken)
+            .ok_or(TandaError::NotInitialized)?;
+        let token = TokenClient::new(&env, &eurc_token);
+        token.transfer(&buyer, &seller, &premium);
+
+        Self::swap_positions(&env, &tanda, sold.position, bought.position)?;
+        Self::drop_listings(&env, &tanda_id, &seller, &buyer);
+        Self::extend_tanda_ttl(&env, &tanda);
+
+        env.events().publish(
+            (Symbol::new(&env, "position_sold"), seller, buyer),
+            (tanda_id, sold.position, premium),
+        );
+
+        Ok(())
+    }
+
+    /// Bid a discount on the current cycle's pot (auction mode). Only active
+    /// members who haven't received can bid; bidding again replaces the bid.
+    /// Bids are open and can be read with `get_bids`; sealed auctions take
+    /// `commit_bid` and `reveal_bid` instead.
+    pub fn place_bid(
+        env: Env,
+        user: Address,
+        tanda_id: String,
+        discount: i128,
+    ) -> Result<(), TandaError> {
+        user.require_auth();
+
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+        Self::check_bidder(&env, &tanda, &user)?;
+        if tanda.sealed_bids {
+            return Err(TandaError::WrongBidMode);
+        }
+
+        Self::record_bid(&env, &tanda, &user, discount)
+    }
+
+    /// Commit a sealed bid on the current cycle's pot: `sha256` of the discount
+    /// (16-byte big-endian i128) followed by a 32-byte salt. Committing again
+    /// replaces the bid, until `close_bids` is called.
+    pub fn commit_bid(
+        env: Env,
+        user: Address,
+        tanda_id: String,
+        commitment: BytesN<32>,
+    ) -> Result<(), TandaError> {
+        user.require_auth();
+
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+        Self::check_bidder(&env, &tanda, &user)?;
+        if !tanda.sealed_bids {
+            return Err(TandaError::WrongBidMode);
+        }
+        if Self::reveal_deadline(&env, &tanda).is_some() {
+            return Err(TandaError::BiddingClosed);
+        }
+
+        let key = DataKey::SealedBids(tanda_id.clone(), tanda.current_cycle);
+        let mut sealed: Map<Address, BytesN<32>> = env.storage().persistent()
+            .get(&key)
+            .unwrap_or(Map::new(&env));
+        sealed.set(user.clone(), commitment);
+        env.storage().persistent().set(&key, &sealed);
+        Self::extend_tanda_ttl(&env, &tanda);
+
+        env.events().publish(
+            (Symbol::new(&env, "bid_committed"), user),
+            (tanda_id, tanda.current_cycle),
+        );
+
+        Ok(())
+    }
+
+    /// Close sealed bidding on the current cycle once everyone has deposited,
+    /// opening a `REVEAL_WINDOW` for bidders to reveal (anyone can call)
+    pub fn close_bids(env: Env, tanda_id: String) -> Result<u64, TandaError> {
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+
+        if tanda.status != TandaStatus::Active {
+            return Err(TandaError::NotActive);
+        }
+        if !tanda.sealed_bids {
+            return Err(TandaError::WrongBidMode);
+        }
+        if Self::reveal_deadline(&env, &tanda).is_some() {
+            return Err(TandaError::BiddingClosed);
+        }
+        if !Self::all_deposited_internal(&env, &tanda) {
+            return Err(TandaError::NotAllDeposited);
+        }
+
+        let deadline = env.ledger().timestamp() + REVEAL_WINDOW;
+        let key = DataKey::RevealDeadline(tanda_id.clone(), tanda.current_cycle);
+        env.storage().persistent().set(&key, &deadline);
+        Self::extend_tanda_ttl(&env, &tanda);
+
+        env.events().publish(
+            (Symbol::new(&env, "bids_closed"),),
+            (tanda_id, tanda.current_cycle, deadline),
+        );
+
+        Ok(deadline)
+    }
+
+    /// Reveal a sealed bid during the reveal window. The discount and salt must
+    /// hash to the committed value; the bid then counts as an open bid.
+    pub fn reveal_bid(
+        env: Env,
+        user: Address,
+        tanda_id: String,
+        discount: i128,
+        salt: BytesN<32>,
+    ) -> Result<(), TandaError> {
+        user.require_auth();
+
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+        Self::check_bidder(&env, &tanda, &user)?;
+        match Self::reveal_deadline(&env, &tanda) {
+            Some(deadline) if env.ledger().timestamp() < deadline => {}
+            _ => return Err(TandaError::RevealNotOpen),
+        }
+
+        let key = DataKey::SealedBids(tanda_id.clone(), tanda.current_cycle);
+        let mut sealed: Map<Address, BytesN<32>> = env.storage().persistent()
+            .get(&key)
+            .unwrap_or(Map::new(&env));
+        let commitment = sealed.get(user.clone()).ok_or(TandaError::BidMismatch)?;
+
+        let mut preimage = Bytes::from_array(&env, &discount.to_be_bytes());
+        preimage.append(&salt.into());
+        let hash: BytesN<32> = env.crypto().sha256(&preimage).into();
+        if hash != commitment {
+            return Err(TandaError::BidMismatch);
+        }
+
+        sealed.remove(user.clone());
+        env.storage().persistent().set(&key, &sealed);
+
+        Self::record_bid(&env, &tanda, &user, discount)
+    }
+
+    /// Extend the storage TTL of a tanda so it is not archived (anyone can call)
+    pub fn bump(env: Env, tanda_id: String) -> Result<(), TandaError> {
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+        Self::extend_members_ttl(&env, &tanda);
+
+        Ok(())
+    }
+
+    // ==================== VIEW FUNCTIONS ====================
+
+    /// Get tanda details
+    pub fn get_tanda(env: Env, tanda_id: String) -> Result<Tanda, TandaError> {
+        Self::get_tanda_internal(&env, &tanda_id)
+    }
+
+    /// Get tanda members (in join order, one record per slot)
+    pub fn get_members(env: Env, tanda_id: String) -> Result<Vec<Member>, TandaError> {
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+        Self::get_members_internal(&env, &tanda)
+    }
+
+    /// Check if all members have deposited for current cycle
+    pub fn all_deposited(env: Env, tanda_id: String) -> Result<bool, TandaError> {
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+        Ok(Self::all_deposited_internal(&env, &tanda))
+    }
+
+    /// Get EURC currently held in escrow for a tanda's open cycle
+    pub fn get_escrow(env: Env, tanda_id: String) -> i128 {
+        env.storage().persistent()
+            .get(&DataKey::Escrow(tanda_id))
+            .unwrap_or(0)
+    }
+
+    /// Get what each member has paid into a given cycle
+    pub fn get_contributions(env: Env, tanda_id: String, cycle: u32) -> Map<Address, i128> {
+        let mut contributions = Map::new(&env);
+        for address in Self::get_member_list(&env, &tanda_id).iter() {
+            let paid = Self::get_contribution(&env, &tanda_id, cycle, &address);
+            if paid > 0 {
+                contributions.set(address, paid);
+            }
+        }
+        contributions
+    }
+
+    /// Get the discount bids placed on a cycle (auction mode)
+    pub fn get_bids(env: Env, tanda_id: String, cycle: u32) -> Map<Address, i128> {
+        env.storage().persistent()
+            .get(&DataKey::Bids(tanda_id, cycle))
+            .unwrap_or(Map::new(&env))
+    }
+
+    /// Get the sealed bids on a cycle that haven't been revealed yet
+    pub fn get_sealed_bids(env: Env, tanda_id: String, cycle: u32) -> Map<Address, BytesN<32>> {
+        env.storage().persistent()
+            .get(&DataKey::SealedBids(tanda_id, cycle))
+            .unwrap_or(Map::new(&env))
+    }
+
+    /// Get the winner, discount and per-member dividend of a settled cycle (auction mode)
+    pub fn get_auction_result(env: Env, tanda_id: String, cycle: u32) -> Option<AuctionResult> {
+        env.storage().persistent().get(&DataKey::AuctionResult(tanda_id, cycle))
+    }
+
+    /// Get how many prepaid cycles each member has left
+    pub fn get_prepaid(env: Env, tanda_id: String) -> Map<Address, u32> {
+        let mut remaining = Map::new(&env);
+        for (address, prepayment) in Self::get_prepaid_internal(&env, &tanda_id).iter() {
+            remaining.set(address, prepayment.cycles);
+        }
+        remaining
+    }
+
+    /// Get the safety fund balance of a tanda
+    pub fn get_safety_fund(env: Env, tanda_id: String) -> i128 {
+        env.storage().persistent()
+            .get(&DataKey::SafetyFund(tanda_id))
+            .unwrap_or(0)
+    }
+
+    /// Get the safety fund movements of a tanda (oldest first)
+    pub fn get_safety_fund_history(env: Env, tanda_id: String) -> Vec<FundEntry> {
+        env.storage().persistent()
+            .get(&DataKey::SafetyFundHistory(tanda_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the pending (not yet expired) join requests of a tanda
+    pub fn get_join_requests(env: Env, tanda_id: String) -> Vec<JoinRequest> {
+        let now = env.ledger().timestamp();
+        let mut pending = Vec::new(&env);
+        for request in Self::get_join_requests_internal(&env, &tanda_id).iter() {
+            if request.expires_at > now {
+                pending.push_back(request);
+            }
+        }
+        pending
+    }
+
+    /// Get the waitlist of a tanda (first in line first)
+    pub fn get_waitlist(env: Env, tanda_id: String) -> Vec<WaitlistEntry> {
+        env.storage().persistent()
+            .get(&DataKey::Waitlist(tanda_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get the open swap proposals of a tanda (proposer -> proposed partner)
+    pub fn get_swap_proposals(env: Env, tanda_id: String) -> Map<Address, Address> {
+        env.storage().persistent()
+            .get(&DataKey::SwapProposals(tanda_id))
+            .unwrap_or(Map::new(&env))
+    }
+
+    /// Get the positions for sale and their premiums (lapsed listings are left out)
+    pub fn get_listings(env: Env, tanda_id: String) -> Result<Map<Address, i128>, TandaError> {
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+        let mut live = Map::new(&env);
+        for (seller, premium) in Self::get_listings_internal(&env, &tanda_id).iter() {
+            if let Some(member) = Self::next_slot(&env, &tanda, &seller)? {
+                if Self::listing_live(&tanda, &member) {
+                    live.set(seller, premium);
+                }
+            }
+        }
+        Ok(live)
+    }
+
+    /// Get the invite codes (hashes only) still registered on a tanda
+    pub fn get_invite_codes(env: Env, tanda_id: String) -> Vec<InviteCode> {
+        Self::get_invite_codes_internal(&env, &tanda_id).values()
+    }
+
+    /// Get the collateral bonds currently held for a tanda's members
+    pub fn get_collateral(env: Env, tanda_id: String) -> Map<Address, i128> {
+        Self::get_collateral_internal(&env, &tanda_id)
+    }
+
+    /// Get the part of the contract's EURC balance not owed to any tanda
+    /// (for reconciling the contract balance against open tandas)
+    pub fn get_unallocated_balance(env: Env) -> Result<i128, TandaError> {
+        let eurc_token: Address = env.storage().instance()
+            .get(&DataKey::EurcToken)
+            .ok_or(TandaError::NotInitialized)?;
+        let total_escrow: i128 = env.storage().instance()
+            .get(&DataKey::TotalEscrow)
+            .unwrap_or(0);
+
+        let token = TokenClient::new(&env, &eurc_token);
+        Ok(token.balance(&env.current_contract_address()) - total_escrow)
+    }
+
+    /// Estimate how many ledgers a tanda's entries stay live without another
+    /// call. Counted from the last full extension (which left every entry
+    /// at least `TANDA_LIFETIME_THRESHOLD` ledgers), so it is a lower bound,
+    /// not any entry's actual live-until ledger.
+    pub fn get_ttl(env: Env, tanda_id: String) -> Result<u32, TandaError> {
+        Self::get_tanda_internal(&env, &tanda_id)?;
+
+        let extended_at: u32 = env.storage().persistent()
+            .get(&DataKey::TtlExtendedAt(tanda_id))
+            .unwrap_or(0);
+        let live_until = extended_at + TANDA_LIFETIME_THRESHOLD;
+
+        Ok(live_until.saturating_sub(env.ledger().sequence()))
+    }
+
+    /// Get current beneficiary (who will receive next payout).
+    /// Lottery and auction tandas have none until the payout settles one.
+    pub fn get_beneficiary(env: Env, tanda_id: String) -> Result<Address, TandaError> {
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+        if Self::chosen_at_payout(&tanda) {
+            return Err(TandaError::BeneficiaryNotFound);
+        }
+        Ok(Self::find_beneficiary(&env, &tanda)?.address)
+    }
+
+    /// Check if a member can be expelled (delinquency period and grace window passed without deposit)
+    pub fn can_expel(env: Env, tanda_id: String, member: Address) -> Result<bool, TandaError> {
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+
+        if tanda.status != TandaStatus::Active {
+            return Ok(false);
+        }
+
+        let now = env.ledger().timestamp();
+        let deadline = Self::expulsion_deadline(&tanda);
+
+        if now < deadline {
+            return Ok(false);
+        }
+
+        match Self::get_slots(&env, &tanda, &member) {
+            Ok(slots) => Ok(slots.iter().any(|m| {
+                let owed = Self::owed_by(&env, &tanda, &m, &member).unwrap_or(0);
+                m.status != MemberStatus::Expelled && !m.has_deposited && owed > 0
+            })),
+            Err(_) => Ok(false),
+        }
+    }
+
+    /// Get the due date of the current cycle (0 unless the tanda runs on a fixed schedule)
+    pub fn get_due_date(env: Env, tanda_id: String) -> Result<u64, TandaError> {
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+
+        if tanda.schedule != Schedule::Fixed || tanda.status != TandaStatus::Active {
+            return Ok(0);
+        }
+
+        Ok(Self::due_date(&tanda))
+    }
+
+    /// Get the members who agreed to pay the current cycle out early
+    pub fn get_early_consents(env: Env, tanda_id: String) -> Result<Vec<Address>, TandaError> {
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+
+        Ok(env.storage().persistent()
+            .get(&DataKey::EarlyConsents(tanda_id, tanda.current_cycle))
+            .unwrap_or(Vec::new(&env)))
+    }
+
+    /// Get seconds until delinquency deadline (when late fees start; expulsion
+    /// waits for the grace window on top)
+    pub fn time_to_deadline(env: Env, tanda_id: String) -> Result<u64, TandaError> {
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+        let now = env.ledger().timestamp();
+        let deadline = Self::delinquency_deadline(&tanda);
+
+        if now >= deadline {
+            return Ok(0);
+        }
+
+        Ok(deadline - now)
+    }
+
+    /// Get status for "Advance" button - what will happen if called
+    pub fn get_advance_status(env: Env, tanda_id: String) -> Result<AdvanceStatus, TandaError> {
+        let tanda: Tanda = Self::get_tanda_internal(&env, &tanda_id)?;
+
+        if tanda.status != TandaStatus::Active {
+            return Ok(AdvanceStatus {
+                can_advance: false,
+                will_expel_count: 0,
+                will_payout: false,
+                beneficiary: None,
+                in_grace: false,
+                grace_ends_at: 0,
+            });
+        }
+
+        let now = env.ledger().timestamp();
+        let deadline = Self::expulsion_deadline(&tanda);
+        let deadline_passed = now >= deadline;
+        let in_grace = now >= Self::delinquency_deadline(&tanda) && !deadline_passed;
+
+        // Count who would be expelled
+        let mut expel_count: u32 = 0;
+        let mut remaining_deposited: u32 = 0;
+        let mut remaining_total: u32 = 0;
+        let mut beneficiary: Option<Address> = None;
+        let beneficiary_position = tanda.current_cycle - 1;
+
+        for id in Self::get_positions(&env, &tanda_id).iter() {
+            let m = Self::get_slot(&env, &tanda, &id)?;
+            if m.status == MemberStatus::Expelled {
+                continue;
+            }
+
+            // A shared slot an owner paid for only loses the other owners
+            let kept = Self::partly_paid(&env, &tanda, &m);
+            let would_be_expelled = deadline_passed && !m.has_deposited && !kept;
+
+            if would_be_expelled {
+                expel_count += 1;
+            } else {
+                remaining_total += 1;
+                if m.has_deposited || (deadline_passed && kept) {
+                    remaining_deposited += 1;
+                }
+                // Find beneficiary among non-expelled
+                if m.position == beneficiary_position
+                    && m.status == MemberStatus::Active
+                    && !Self::chosen_at_payout(&tanda)
+                {
+                    beneficiary = Some(m.address.clone());
+                }
+            }
+        }
+
+        // Would payout happen after expulsions?
+        let will_payout = remaining_total > 1
+            && remaining_deposited == remaining_total
+            && Self::payout_due(&env, &tanda);
+
+        // Can advance if something would happen
+        let can_advance = expel_count > 0 || will_payout;
+
+        Ok(AdvanceStatus {
+            can_advance,
+            will_expel_count: expel_count,
+            will_payout,
+            beneficiary,
+            in_grace,
+            grace_ends_at: deadline,
+        })
+    }
+
+    // ==================== INTERNAL HELPERS ====================
+
+    fn get_tanda_internal(env: &Env, tanda_id: &String) -> Result<Tanda, TandaError> {
+        env.storage().persistent()
+            .get(&DataKey::Tanda(tanda_id.clone()))
+            .ok_or(TandaError::TandaNotFound)
+    }
+
+    /// Due date of the current cycle on a fixed schedule
+    fn due_date(tanda: &Tanda) -> u64 {
+        tanda.started_at + tanda.current_cycle as u64 * tanda.period_days * SECONDS_PER_DAY
+    }
+
+    /// When members who haven't deposited become late: `delinquency_days`
+    /// after the last payout, or after the due date on a fixed schedule
+    fn delinquency_deadline(tanda: &Tanda) -> u64 {
+        let grace = tanda.delinquency_days * SECONDS_PER_DAY;
+        match tanda.schedule {
+            Schedule::WhenAllPaid => tanda.last_payout_at + grace,
+            Schedule::Fixed => Self::due_date(tanda) + grace,
+        }
+    }
+
+    /// When members who haven't deposited become expellable: the end of the
+    /// grace window after the delinquency deadline
+    fn expulsion_deadline(tanda: &Tanda) -> u64 {
+        Self::delinquency_deadline(tanda) + tanda.grace_days * SECONDS_PER_DAY
+    }
+
+    /// Whether the current cycle may be paid out once everyone has deposited.
+    /// Consents can't include expelled members: nobody is expelled before the due date.
+    fn payout_due(env: &Env, tanda: &Tanda) -> bool {
+        if !Self::bids_revealed(env, tanda) {
+            return false;
+        }
+        if tanda.schedule != Schedule::Fixed || env.ledger().timestamp() >= Self::due_date(tanda) {
+            return true;
+        }
+
+        let consents: Vec<Address> = env.storage().persistent()
+            .get(&DataKey::EarlyConsents(tanda.id.clone(), tanda.current_cycle))
+            .unwrap_or(Vec::new(env));
+
+        // A consent speaks for every slot the member still holds (or shares)
+        let mut agreed: Map<SlotId, bool> = Map::new(env);
+        for address in consents.iter() {
+            if let Ok(slots) = Self::get_slots(env, tanda, &address) {
+                for m in slots.iter().filter(|m| m.status != MemberStatus::Expelled) {
+                    agreed.set(SlotId { address: m.address, slot: m.slot }, true);
+                }
+            }
+        }
+        agreed.len() >= tanda.active_members
+    }
+
+    fn extend_instance_ttl(env: &Env) {
+        env.storage().instance().extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Keep the contract instance and the shared entries of a tanda from being
+    /// archived. Member records are extended whenever they are written, and
+    /// feature entries only if the tanda's settings can create them, so
+    /// plain tandas don't pay for keys they never use.
+    fn extend_tanda_ttl(env: &Env, tanda: &Tanda) {
+        Self::extend_instance_ttl(env);
+
+        let id = &tanda.id;
+        let storage = env.storage().persistent();
+        let extend = |key: DataKey| {
+            if storage.has(&key) {
+                storage.extend_ttl(&key, TANDA_LIFETIME_THRESHOLD, TANDA_BUMP_AMOUNT);
+            }
+        };
+
+        for key in [
+            DataKey::Tanda(id.clone()),
+            DataKey::MemberList(id.clone()),
+            DataKey::Positions(id.clone()),
+            DataKey::Deposits(id.clone(), tanda.current_cycle),
+            DataKey::SafetyFund(id.clone()),
+            DataKey::SafetyFundShares(id.clone()),
+            DataKey::SafetyFundHistory(id.clone()),
+            DataKey::Escrow(id.clone()),
+            DataKey::TtlExtendedAt(id.clone()),
+        ] {
+            extend(key);
+        }
+
+        if tanda.collateral_multiple > 0 {
+            extend(DataKey::Collateral(id.clone()));
+        }
+        if tanda.visibility == Visibility::ApprovalRequired {
+            extend(DataKey::JoinRequests(id.clone()));
+        }
+        if tanda.invite_only {
+            extend(DataKey::InviteCodes(id.clone()));
+        }
+        if tanda.forming_deadline > 0 {
+            extend(DataKey::CreationFee(id.clone()));
+        }
+        if tanda.schedule == Schedule::Fixed {
+            extend(DataKey::EarlyConsents(id.clone(), tanda.current_cycle));
+        }
+        if tanda.payout_order == PayoutOrder::Auction {
+            extend(DataKey::Bids(id.clone(), tanda.current_cycle));
+        }
+        if tanda.sealed_bids {
+            extend(DataKey::SealedBids(id.clone(), tanda.current_cycle));
+            extend(DataKey::RevealDeadline(id.clone(), tanda.current_cycle));
+        }
+        if !Self::chosen_at_payout(tanda) {
+            extend(DataKey::SwapProposals(id.clone()));
+            extend(DataKey::Listings(id.clone()));
+        }
+        if tanda.commitment_mode != CommitmentMode::Proportional {
+            extend(DataKey::Prepaid(id.clone()));
+        }
+        if tanda.commitment_mode == CommitmentMode::Equal
+            && tanda.visibility == Visibility::Open
+            && !tanda.invite_only
+        {
+            extend(DataKey::Waitlist(id.clone()));
+        }
+        if tanda.renewed_from.is_some() {
+            extend(DataKey::Reservations(id.clone()));
+        }
+        if tanda.commitment_mode == CommitmentMode::Proportional {
+            extend(DataKey::CycleScale(id.clone()));
+        }
+    }
+
+    /// Extend every entry of a tanda, including all member records. Done once
+    /// per cycle (and by `bump`), so members who stop acting are not archived.
+    fn extend_members_ttl(env: &Env, tanda: &Tanda) {
+        let storage = env.storage().persistent();
+        storage.set(&DataKey::TtlExtendedAt(tanda.id.clone()), &env.ledger().sequence());
+
+        for address in Self::get_member_list(env, &tanda.id).iter() {
+            for slot in 0..Self::slot_count(env, &tanda.id, &address) {
+                let key = Self::member_key(&tanda.id, &address, slot);
+                storage.extend_ttl(&key, TANDA_LIFETIME_THRESHOLD, TANDA_BUMP_AMOUNT);
+            }
+            let key = DataKey::CoOwner(tanda.id.clone(), address);
+            if storage.has(&key) {
+                storage.extend_ttl(&key, TANDA_LIFETIME_THRESHOLD, TANDA_BUMP_AMOUNT);
+            }
+        }
+
+        Self::extend_tanda_ttl(env, tanda);
+    }
+
+    fn get_member_list(env: &Env, tanda_id: &String) -> Vec<Address> {
+        env.storage().persistent()
+            .get(&DataKey::MemberList(tanda_id.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn get_positions(env: &Env, tanda_id: &String) -> Vec<SlotId> {
+        env.storage().persistent()
+            .get(&DataKey::Positions(tanda_id.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Storage key of one of a member's slots (the first keeps the plain member key)
+    fn member_key(tanda_id: &String, address: &Address, slot: u32) -> DataKey {
+        if slot == 0 {
+            DataKey::Member(tanda_id.clone(), address.clone())
+        } else {
+            DataKey::MemberSlot(tanda_id.clone(), address.clone(), slot)
+        }
+    }
+
+    /// Number of slots an address holds (0 if it isn't a member)
+    fn slot_count(env: &Env, tanda_id: &String, address: &Address) -> u32 {
+        let mut count = 0;
+        while env.storage().persistent().has(&Self::member_key(tanda_id, address, count)) {
+            count += 1;
+        }
+        count
+    }
+
+    /// Load a member's first slot
+    fn get_member_internal(env: &Env, tanda: &Tanda, address: &Address) -> Result<Member, TandaError> {
+        Self::get_slot(env, tanda, &SlotId { address: address.clone(), slot: 0 })
+    }
+
+    /// Load a slot record. Deposit fields left over from an earlier cycle
+    /// read as nothing paid, so payouts never have to reset every member.
+    fn get_slot(env: &Env, tanda: &Tanda, id: &SlotId) -> Result<Member, TandaError> {
+        let mut member: Member = env.storage().persistent()
+            .get(&Self::member_key(&tanda.id, &id.address, id.slot))
+            .ok_or(TandaError::NotMember)?;
+
+        if member.deposit_cycle != tanda.current_cycle {
+            member.has_deposited = false;
+            member.paid_this_cycle = 0;
+            member.shares_paid = Map::new(env);
+        }
+
+        Ok(member)
+    }
+
+    /// Load every slot an address holds, first slot first (for a co-owner,
+    /// the shared slot they hold a share of)
+    fn get_slots(env: &Env, tanda: &Tanda, address: &Address) -> Result<Vec<Member>, TandaError> {
+        let mut slots = Vec::new(env);
+        if let Some(id) = Self::co_owned_slot(env, &tanda.id, address) {
+            slots.push_back(Self::get_slot(env, tanda, &id)?);
+            return Ok(slots);
+        }
+
+        slots.push_back(Self::get_member_internal(env, tanda, address)?);
+        for slot in 1..Self::slot_count(env, &tanda.id, address) {
+            slots.push_back(Self::get_slot(env, tanda, &SlotId { address: address.clone(), slot })?);
+        }
+        Ok(slots)
+    }
+
+    /// The slot of an address paid out next: its earliest one still waiting
+    fn next_slot(env: &Env, tanda: &Tanda, address: &Address) -> Result<Option<Member>, TandaError> {
+        let mut next: Option<Member> = None;
+        for m in Self::get_slots(env, tanda, address)?.iter() {
+            let earlier = next.as_ref().is_none_or(|n| m.position < n.position);
+            if m.status == MemberStatus::Active && earlier {
+                next = Some(m);
+            }
+        }
+        Ok(next)
+    }
+
+    /// The shared slot an address co-owns without holding it
+    fn co_owned_slot(env: &Env, tanda_id: &String, address: &Address) -> Option<SlotId> {
+        env.storage().persistent().get(&DataKey::CoOwner(tanda_id.clone(), address.clone()))
+    }
+
+    /// Point the co-owners of a new shared slot at it (the slot is kept under
+    /// its holder) and add them to the join list
+    fn link_co_owners(env: &Env, tanda_id: &String, member: &Member) {
+        let id = SlotId { address: member.address.clone(), slot: member.slot };
+        let mut list = Self::get_member_list(env, tanda_id);
+        for owner in member.shares.keys().iter() {
+            if owner != member.address {
+                let key = DataKey::CoOwner(tanda_id.clone(), owner.clone());
+                env.storage().persistent().set(&key, &id);
+                list.push_back(owner);
+            }
+        }
+        env.storage().persistent().set(&DataKey::MemberList(tanda_id.clone()), &list);
+    }
+
+    /// Whether an address holds a slot, or a share of one
+    fn is_member(env: &Env, tanda_id: &String, address: &Address) -> bool {
+        Self::slot_count(env, tanda_id, address) > 0
+            || Self::co_owned_slot(env, tanda_id, address).is_some()
+    }
+
+    /// Everyone who still owns a slot: its holder, or the co-owners left in a shared one
+    fn owners(env: &Env, member: &Member) -> Vec<Address> {
+        if member.shares.is_empty() {
+            Vec::from_array(env, [member.address.clone()])
+        } else {
+            member.shares.keys()
+        }
+    }
+
+    /// Divide `total` among a slot's owners by share (the last one also takes
+    /// the rounding remainder). Co-owners who defaulted have no share left,
+    /// so the parts can add up to less than `total`.
+    fn split_by_share(env: &Env, member: &Member, total: i128) -> Map<Address, i128> {
+        let mut parts = Map::new(env);
+        if member.shares.is_empty() {
+            parts.set(member.address.clone(), total);
+            return parts;
+        }
+
+        let owned_bps: u32 = member.shares.values().iter().sum();
+        let mut left = total * owned_bps as i128 / 10000;
+        for (i, (owner, bps)) in member.shares.iter().enumerate() {
+            let part = if i as u32 == member.shares.len() - 1 {
+                left
+            } else {
+                total * bps as i128 / 10000
+            };
+            left -= part;
+            parts.set(owner, part);
+        }
+        parts
+    }
+
+    /// Scale applied to commitments in the open cycle of a proportional tanda
+    /// (see `store_cycle_scale`). None when dues are the commitments themselves.
+    fn cycle_scale(env: &Env, tanda: &Tanda) -> Option<(i128, i128)> {
+        if tanda.commitment_mode != CommitmentMode::Proportional {
+            return None;
+        }
+
+        Some(env.storage().persistent()
+            .get(&DataKey::CycleScale(tanda.id.clone()))
+            .unwrap_or((0, 1)))
+    }
+
+    /// Work out the scale of a proportional tanda's open cycle: the
+    /// beneficiary's commitment times the slots in the rotation, over the sum
+    /// of every slot's commitment. It reads every slot, so it is stored
+    /// whenever the cycle or the payout order changes rather than on each deposit.
+    fn store_cycle_scale(env: &Env, tanda: &Tanda) -> Result<(), TandaError> {
+        if tanda.commitment_mode != CommitmentMode::Proportional {
+            return Ok(());
+        }
+
+        let positions = Self::get_positions(env, &tanda.id);
+        let mut total: i128 = 0;
+        for id in positions.iter() {
+            total += Self::get_slot(env, tanda, &id)?.commitment;
+        }
+        let beneficiary = match positions.get(tanda.current_cycle.wrapping_sub(1)) {
+            Some(id) => Self::get_slot(env, tanda, &id)?.commitment,
+            None => 0,
+        };
+
+        let key = DataKey::CycleScale(tanda.id.clone());
+        env.storage().persistent().set(&key, &(beneficiary * positions.len() as i128, total));
+        env.storage().persistent().extend_ttl(&key, TANDA_LIFETIME_THRESHOLD, TANDA_BUMP_AMOUNT);
+
+        Ok(())
+    }
+
+    /// What a commitment pays into the open cycle, given `cycle_scale`
+    fn commitment_due(commitment: i128, scale: &Option<(i128, i128)>) -> i128 {
+        match scale {
+            Some((num, den)) if *den > 0 => commitment * num / den,
+            _ => commitment,
+        }
+    }
+
+    /// What a slot pays into the open cycle before any co-owner defaults
+    fn cycle_due(env: &Env, tanda: &Tanda, member: &Member) -> i128 {
+        Self::commitment_due(member.commitment, &Self::cycle_scale(env, tanda))
+    }
+
+    /// What the open cycle's pot holds once every slot in the rotation has paid
+    fn full_pot(env: &Env, tanda: &Tanda) -> i128 {
+        let scale = Self::cycle_scale(env, tanda);
+        let mut pot: i128 = 0;
+        for id in Self::get_positions(env, &tanda.id).iter() {
+            if let Ok(m) = Self::get_slot(env, tanda, &id) {
+                pot += Self::commitment_due(m.commitment, &scale);
+            }
+        }
+        pot
+    }
+
+    /// What a slot owes per cycle, less any defaulted co-owner's share
+    fn slot_due(env: &Env, tanda: &Tanda, member: &Member) -> i128 {
+        let due = Self::cycle_due(env, tanda, member);
+        Self::split_by_share(env, member, due).values().iter().sum()
+    }
+
+    /// What `payer` still owes on a slot for the open cycle (None if they don't own it)
+    fn owed_by(env: &Env, tanda: &Tanda, member: &Member, payer: &Address) -> Option<i128> {
+        if member.shares.is_empty() {
+            return (member.address == *payer)
+                .then(|| Self::cycle_due(env, tanda, member) - member.paid_this_cycle);
+        }
+
+        let due = Self::cycle_due(env, tanda, member);
+        let due = Self::split_by_share(env, member, due).get(payer.clone())?;
+        Some(due - member.shares_paid.get(payer.clone()).unwrap_or(0))
+    }
+
+    /// Whether a shared slot still short of its due has an owner who paid their full share
+    fn partly_paid(env: &Env, tanda: &Tanda, member: &Member) -> bool {
+        !member.has_deposited
+            && member.shares.keys().iter()
+                .any(|owner| Self::owed_by(env, tanda, member, &owner) == Some(0))
+    }
+
+    /// Pay `amount` already taken out of the tanda's balances to a slot, split
+    /// among its owners by share. The share of co-owners who defaulted goes to
+    /// the safety fund.
+    fn pay_slot(env: &Env, tanda: &Tanda, member: &Member, amount: i128) -> Result<(), TandaError> {
+        let mut forfeited = amount;
+        for (owner, part) in Self::split_by_share(env, member, amount).iter() {
+            if part > 0 {
+                Self::transfer_out(env, &owner, part)?;
+            }
+            forfeited -= part;
+        }
+
+        if forfeited > 0 {
+            Self::adjust_balance(env, DataKey::SafetyFund(tanda.id.clone()), forfeited);
+            Self::log_fund_movement(env, &tanda.id, FundEntry {
+                cycle: tanda.current_cycle,
+                movement: FundMovement::Forfeit,
+                account: None,
+                amount: forfeited,
+                timestamp: env.ledger().timestamp(),
+            });
+        }
+
+        Ok(())
+    }
+
+    fn save_member(env: &Env, tanda_id: &String, member: &Member) {
+        let key = Self::member_key(tanda_id, &member.address, member.slot);
+        env.storage().persistent().set(&key, member);
+        env.storage().persistent().extend_ttl(&key, TANDA_LIFETIME_THRESHOLD, TANDA_BUMP_AMOUNT);
+    }
+
+    /// Take payments from `payer` toward the open cycle on one or more of
+    /// their slots, in a single transfer
+    fn deposit_internal(
+        env: &Env,
+        tanda: &Tanda,
+        payer: &Address,
+        payments: Vec<(Member, i128)>,
+    ) -> Result<(), TandaError> {
+        if tanda.status != TandaStatus::Active {
+            return Err(TandaError::NotActive);
+        }
+
+        // Check member status (a co-owner dropped from a shared slot counts as expelled)
+        let mut amount: i128 = 0;
+        for (member, paid) in payments.iter() {
+            if member.status == MemberStatus::Expelled {
+                return Err(TandaError::MemberExpelled);
+            }
+            let Some(owed) = Self::owed_by(env, tanda, &member, payer) else {
+                return Err(TandaError::MemberExpelled);
+            };
+            if member.has_deposited || owed == 0 {
+                return Err(TandaError::AlreadyDeposited);
+            }
+            if paid <= 0 || paid > owed {
+                return Err(TandaError::InvalidAmount);
+            }
+            amount += paid;
+        }
+
+        // Get token and addresses
+        let eurc_token: Address = env.storage().instance()
+            .get(&DataKey::EurcToken)
+            .ok_or(TandaError::NotInitialized)?;
+        let commission_addr = Self::get_commission_address(env)?;
+        let commission_bps: u32 = env.storage().instance()
+            .get(&DataKey::CommissionBps)
+            .unwrap_or(50);
+
+        // Calculate amounts (deposits after the delinquency deadline pay a late
+        // fee); fund shares and late fees are worked out slot by slot
+        let commission = (amount * commission_bps as i128) / 10000;
+        let late_fee_bps = if env.ledger().timestamp() >= Self::delinquency_deadline(tanda) {
+            tanda.late_fee_bps as i128
+        } else {
+            0
+        };
+        let mut total = amount;
+        for (_, paid) in payments.iter() {
+            total += (paid * tanda.safety_fund_bps as i128) / 10000 + (paid * late_fee_bps) / 10000;
+        }
+        let token = TokenClient::new(env, &eurc_token);
+
+        // Transfer deposit (plus safety fund share and late fee) to contract
+        token.transfer(payer, &env.current_contract_address(), &total);
+
+        // Transfer commission
+        if commission > 0 {
+            token.transfer(payer, &commission_addr, &commission);
+        }
+
+        for (member, paid) in payments.iter() {
+            let late_fee = (paid * late_fee_bps) / 10000;
+            if late_fee > 0 {
+                Self::collect_late_fee(env, tanda, payer, late_fee);
+            }
+            let fund_share = (paid * tanda.safety_fund_bps as i128) / 10000;
+            Self::credit_deposit(env, tanda, payer, member, paid, fund_share);
+        }
+        Self::extend_tanda_ttl(env, tanda);
+
+        Ok(())
+    }
+
+    /// Route a late fee already in the contract to the cycle's pot or the
+    /// safety fund. It is not part of the member's contribution, so it is
+    /// kept even if their deposit is refunded.
+    fn collect_late_fee(env: &Env, tanda: &Tanda, member: &Address, fee: i128) {
+        match tanda.late_fee_to {
+            LateFeeRecipient::Beneficiary => {
+                Self::adjust_escrow(env, &tanda.id, fee);
+            }
+            LateFeeRecipient::SafetyFund => {
+                Self::adjust_balance(env, DataKey::SafetyFund(tanda.id.clone()), fee);
+                Self::log_fund_movement(env, &tanda.id, FundEntry {
+                    cycle: tanda.current_cycle,
+                    movement: FundMovement::LateFee,
+                    account: Some(member.clone()),
+                    amount: fee,
+                    timestamp: env.ledger().timestamp(),
+                });
+            }
+        }
+
+        env.events().publish(
+            (Symbol::new(env, "late_fee_paid"), member.clone()),
+            (tanda.id.clone(), tanda.current_cycle, fee),
+        );
+    }
+
+    /// Record a payment already in the contract from `payer` toward a slot's contribution
+    fn credit_deposit(
+        env: &Env,
+        tanda: &Tanda,
+        payer: &Address,
+        mut member: Member,
+        amount: i128,
+        fund_share: i128,
+    ) {
+        // Record the contribution in the tanda's escrow
+        Self::record_contribution(env, &tanda.id, tanda.current_cycle, payer, amount);
+        if fund_share > 0 {
+            Self::collect_safety_fund(env, &tanda.id, tanda.current_cycle, payer, fund_share);
+        }
+
+        member.paid_this_cycle += amount;
+        member.deposit_cycle = tanda.current_cycle;
+        if !member.shares.is_empty() {
+            let paid = member.shares_paid.get(payer.clone()).unwrap_or(0);
+            member.shares_paid.set(payer.clone(), paid + amount);
+        }
+
+        // Mark as deposited once the full amount (every owner's share) is in
+        if member.paid_this_cycle == Self::slot_due(env, tanda, &member) {
+            member.has_deposited = true;
+
+            let count = Self::deposit_count(env, &tanda.id, tanda.current_cycle);
+            let deposits_key = DataKey::Deposits(tanda.id.clone(), tanda.current_cycle);
+            env.storage().persistent().set(&deposits_key, &(count + 1));
+
+            env.events().publish(
+                (Symbol::new(env, "deposit_made"), member.address.clone()),
+                (tanda.id.clone(), tanda.current_cycle),
+            );
+        } else {
+            env.events().publish(
+                (Symbol::new(env, "installment_paid"), payer.clone()),
+                (tanda.id.clone(), tanda.current_cycle, member.paid_this_cycle),
+            );
+        }
+
+        Self::save_member(env, &tanda.id, &member);
+    }
+
+    fn get_prepaid_internal(env: &Env, tanda_id: &String) -> Map<Address, Prepayment> {
+        env.storage().persistent()
+            .get(&DataKey::Prepaid(tanda_id.clone()))
+            .unwrap_or(Map::new(env))
+    }
+
+    /// What one prepaid cycle holds besides commission: the member's
+    /// commitment plus the safety fund share
+    fn prepaid_cycle_cost(tanda: &Tanda, commitment: i128) -> i128 {
+        commitment + (commitment * tanda.safety_fund_bps as i128) / 10000
+    }
+
+    /// Use one of a member's prepaid cycles as their deposit for the open cycle
+    fn apply_prepayment(env: &Env, tanda: &Tanda, address: &Address) -> Result<(), TandaError> {
+        let mut prepaid = Self::get_prepaid_internal(env, &tanda.id);
+        let Some(mut prepayment) = prepaid.get(address.clone()) else {
+            return Ok(());
+        };
+
+        let member = Self::get_member_internal(env, tanda, address)?;
+        if member.status == MemberStatus::Expelled || member.paid_this_cycle > 0 {
+            return Ok(());
+        }
+
+        // The last cycle takes whatever commission rounding left over
+        let commission = prepayment.commission / prepayment.cycles as i128;
+        let due = member.commitment;
+        let fund_share = Self::prepaid_cycle_cost(tanda, due) - due;
+        Self::adjust_total_escrow(env, -(due + fund_share + commission));
+        if commission > 0 {
+            Self::transfer_out(env, &Self::get_commission_address(env)?, commission)?;
+        }
+        Self::credit_deposit(env, tanda, address, member, due, fund_share);
+
+        prepayment.cycles -= 1;
+        prepayment.commission -= commission;
+        if prepayment.cycles == 0 {
+            prepaid.remove(address.clone());
+        } else {
+            prepaid.set(address.clone(), prepayment);
+        }
+        env.storage().persistent().set(&DataKey::Prepaid(tanda.id.clone()), &prepaid);
+
+        Ok(())
+    }
+
+    /// Mark every member with prepaid cycles left as deposited for the new cycle
+    fn apply_prepayments(env: &Env, tanda: &Tanda) -> Result<(), TandaError> {
+        for address in Self::get_prepaid_internal(env, &tanda.id).keys().iter() {
+            Self::apply_prepayment(env, tanda, &address)?;
+        }
+        Ok(())
+    }
+
+    /// Return a member's unused prepaid cycles, commission included
+    fn refund_prepayment(env: &Env, tanda: &Tanda, address: &Address) -> Result<(), TandaError> {
+        let mut prepaid = Self::get_prepaid_internal(env, &tanda.id);
+        let Some(prepayment) = prepaid.get(address.clone()) else {
+            return Ok(());
+        };
+        prepaid.remove(address.clone());
+
+        let commitment = Self::get_member_internal(env, tanda, address)?.commitment;
+        let refund = Self::prepaid_cycle_cost(tanda, commitment) * prepayment.cycles as i128
+            + prepayment.commission;
+        Self::transfer_out(env, address, refund)?;
+        Self::adjust_total_escrow(env, -refund);
+        env.storage().persistent().set(&DataKey::Prepaid(tanda.id.clone()), &prepaid);
+
+        env.events().publish(
+            (Symbol::new(env, "prepayment_refunded"), address.clone()),
+            (tanda.id.clone(), prepayment.cycles, refund),
+        );
+
+        Ok(())
+    }
+
+    fn get_commission_address(env: &Env) -> Result<Address, TandaError> {
+        env.storage().instance()
+            .get(&DataKey::CommissionAddress)
+            .ok_or(TandaError::NotInitialized)
+    }
+
+    /// Move a forming tanda to Active with everyone who has joined so far
+    fn start_internal(env: &Env, tanda: &mut Tanda) -> Result<(), TandaError> {
+        let member_count = tanda.active_members;
+
+        if member_count < MIN_MEMBERS {
+            return Err(TandaError::NotEnoughMembers);
+        }
+
+        // Start tanda
+        let now = env.ledger().timestamp();
+        tanda.status = TandaStatus::Active;
+        tanda.started_at = now;
+        tanda.last_payout_at = now; // Start the delinquency clock
+        tanda.current_cycle = 1;
+        tanda.total_cycles = member_count;
+
+        // Positions hold join order (or the creator's assignment) until now
+        let mut positions = Self::get_positions(env, &tanda.id);
+        match tanda.payout_order {
+            PayoutOrder::JoinOrder
+            | PayoutOrder::CreatorAssigned
+            | PayoutOrder::Lottery
+            | PayoutOrder::Auction => {}
+            PayoutOrder::Random => env.prng().shuffle(&mut positions),
+            PayoutOrder::Reverse => {
+                let mut reversed = Vec::new(env);
+                for address in positions.iter() {
+                    reversed.push_front(address);
+                }
+                positions = reversed;
+            }
+        }
+        Self::set_positions(env, tanda, &positions)?;
+
+        env.storage().persistent().set(&DataKey::Tanda(tanda.id.clone()), &*tanda);
+        Self::clear_join_requests(env, &tanda.id)?;
+        let commission_addr = Self::get_commission_address(env)?;
+        Self::release_creation_fee(env, &tanda.id, &commission_addr)?;
+        Self::extend_members_ttl(env, tanda);
+
+        env.events().publish(
+            (Symbol::new(env, "tanda_started"), tanda.creator.clone()),
+            (tanda.id.clone(), tanda.payout_order.clone(), positions),
+        );
+
+        Ok(())
+    }
+
+    /// Cancel a forming tanda: bonds and join requests are returned, and a
+    /// held creation fee goes to `fee_to`
+    fn cancel_internal(env: &Env, tanda: &mut Tanda, fee_to: &Address) -> Result<(), TandaError> {
+        tanda.status = TandaStatus::Cancelled;
+        env.storage().persistent().set(&DataKey::Tanda(tanda.id.clone()), &*tanda);
+
+        for address in Self::get_member_list(env, &tanda.id).iter() {
+            Self::return_collateral(env, &tanda.id, &address, i128::MAX)?;
+        }
+        Self::clear_join_requests(env, &tanda.id)?;
+        Self::release_creation_fee(env, &tanda.id, fee_to)?;
+        Self::extend_tanda_ttl(env, tanda);
+
+        env.events().publish(
+            (Symbol::new(env, "tanda_cancelled"), tanda.creator.clone()),
+            tanda.id.clone(),
+        );
+
+        Ok(())
+    }
+
+    /// Pay out a creation fee held while the tanda was forming
+    fn release_creation_fee(env: &Env, tanda_id: &String, to: &Address) -> Result<(), TandaError> {
+        let key = DataKey::CreationFee(tanda_id.clone());
+        let fee: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if fee <= 0 {
+            return Ok(());
+        }
+
+        Self::transfer_out(env, to, fee)?;
+        Self::adjust_total_escrow(env, -fee);
+        env.storage().persistent().remove(&key);
+
+        Ok(())
+    }
+
+    /// Admit a user to a forming tanda once any invite code has been checked,
+    /// or give an existing member one more slot
+    fn join_internal(env: &Env, user: &Address, mut tanda: Tanda) -> Result<(), TandaError> {
+        if tanda.status != TandaStatus::Forming {
+            return Err(TandaError::NotForming);
+        }
+
+        if Self::seats_taken(env, &tanda) >= tanda.max_members {
+            return Err(TandaError::TandaFull);
+        }
+
+        // Check the member isn't at the slot cap (one slot unless the tanda
+        // allows more; owners of a shared slot hold just that one)
+        let slots = Self::slot_count(env, &tanda.id, user);
+        let shared = slots > 0 && !Self::get_member_internal(env, &tanda, user)?.shares.is_empty();
+        let co_owner = Self::co_owned_slot(env, &tanda.id, user).is_some();
+        if slots >= tanda.max_slots_per_member || shared || co_owner {
+            return Err(TandaError::AlreadyMember);
+        }
+
+        // Someone with a seat reserved in a renewal takes it with `confirm_seat`
+        let reserved = Self::get_reservations(env, &tanda.id).iter()
+            .any(|seat| seat.slot.is_none() && Self::holds_seat(&seat, user));
+        if reserved {
+            return Err(TandaError::RequestPending);
+        }
+
+        // Once approved, a member takes further slots without asking again
+        if slots == 0 && tanda.visibility == Visibility::ApprovalRequired {
+            return Self::request_to_join(env, &tanda, user);
+        }
+
+        // Add member (or their next slot)
+        Self::add_slot(env, &tanda, user, slots, Map::new(env));
+        tanda.active_members += 1;
+        env.storage().persistent().set(&DataKey::Tanda(tanda.id.clone()), &tanda);
+
+        Self::post_collateral(env, &tanda, user, Self::slot_bond(&tanda))?;
+        Self::extend_tanda_ttl(env, &tanda);
+
+        if slots == 0 {
+            env.events().publish(
+                (Symbol::new(env, "member_joined"), user.clone()),
+                tanda.id.clone(),
+            );
+        } else {
+            env.events().publish(
+                (Symbol::new(env, "slot_added"), user.clone()),
+                (tanda.id.clone(), slots),
+            );
+        }
+
+        Self::start_if_full(env, &mut tanda)
+    }
+
+    /// Slots taken, counting seats still reserved for a renewal
+    fn seats_taken(env: &Env, tanda: &Tanda) -> u32 {
+        let pending = Self::get_reservations(env, &tanda.id).iter()
+            .filter(|r| r.slot.is_none())
+            .count() as u32;
+        tanda.active_members + pending
+    }
+
+    /// Whether `user` is an owner of a reserved seat
+    fn holds_seat(seat: &Reservation, user: &Address) -> bool {
+        if seat.shares.is_empty() {
+            seat.address == *user
+        } else {
+            seat.shares.contains_key(user.clone())
+        }
+    }
+
+    fn get_reservations(env: &Env, tanda_id: &String) -> Vec<Reservation> {
+        env.storage().persistent()
+            .get(&DataKey::Reservations(tanda_id.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Seat `user` in every reservation they hold or share, posting bonds as on
+    /// joining (co-owners of a shared seat sign too). With a fixed payout order
+    /// confirmed seats go first, in the order they were reserved; otherwise
+    /// they are drawn, shuffled or bid for like any other slot. Returns the
+    /// number of seats taken.
+    fn confirm_reservations(env: &Env, tanda: &mut Tanda, user: &Address) -> Result<u32, TandaError> {
+        let mut reservations = Self::get_reservations(env, &tanda.id);
+        let mut confirmed: u32 = 0;
+        for i in 0..reservations.len() {
+            let mut seat = reservations.get(i).unwrap();
+            if seat.slot.is_some() || !Self::holds_seat(&seat, user) {
+                continue;
+            }
+            for owner in seat.shares.keys().iter() {
+                if owner != *user {
+                    owner.require_auth();
+                }
+            }
+
+            let slot = Self::slot_count(env, &tanda.id, &seat.address);
+            let mut member = Self::add_slot(env, tanda, &seat.address, slot, seat.shares.clone());
+            member.commitment = seat.commitment;
+            Self::save_member(env, &tanda.id, &member);
+            Self::link_co_owners(env, &tanda.id, &member);
+            tanda.active_members += 1;
+
+            for (owner, bond) in Self::split_by_share(env, &member, Self::slot_bond(tanda)).iter() {
+                Self::post_collateral(env, tanda, &owner, bond)?;
+            }
+
+            seat.slot = Some(slot);
+            reservations.set(i, seat);
+            confirmed += 1;
+        }
+        if confirmed == 0 {
+            return Ok(0);
+        }
+        env.storage().persistent().set(&DataKey::Reservations(tanda.id.clone()), &reservations);
+        env.storage().persistent().set(&DataKey::Tanda(tanda.id.clone()), &*tanda);
+        if matches!(
+            tanda.payout_order,
+            PayoutOrder::JoinOrder | PayoutOrder::CreatorAssigned | PayoutOrder::Reverse
+        ) {
+            Self::order_reserved_seats(env, tanda, &reservations)?;
+        }
+        Self::extend_members_ttl(env, tanda);
+
+        env.events().publish(
+            (Symbol::new(env, "seat_confirmed"), user.clone()),
+            (tanda.id.clone(), confirmed),
+        );
+
+        Ok(confirmed)
+    }
+
+    /// Put confirmed reserved seats ahead of anyone who joined in between, in
+    /// the order they were reserved. A reverse tanda flips its positions on
+    /// start, so they are stored the other way round.
+    fn order_reserved_seats(
+        env: &Env,
+        tanda: &Tanda,
+        reservations: &Vec<Reservation>,
+    ) -> Result<(), TandaError> {
+        let positions = Self::get_positions(env, &tanda.id);
+        let mut reserved = Vec::new(env);
+        for seat in reservations.iter() {
+            if let Some(slot) = seat.slot {
+                let id = SlotId { address: seat.address, slot };
+                if positions.contains(&id) {
+                    reserved.push_back(id);
+                }
+            }
+        }
+
+        let mut order = Vec::new(env);
+        if tanda.payout_order == PayoutOrder::Reverse {
+            for id in positions.iter() {
+                if !reserved.contains(&id) {
+                    order.push_back(id);
+                }
+            }
+            for id in reserved.iter().rev() {
+                order.push_back(id);
+            }
+        } else {
+            order = reserved.clone();
+            for id in positions.iter() {
+                if !reserved.contains(&id) {
+                    order.push_back(id);
+                }
+            }
+        }
+        Self::set_positions(env, tanda, &order)
+    }
+
+    /// Start a tanda that asked to start itself once its last slot is filled
+    fn start_if_full(env: &Env, tanda: &mut Tanda) -> Result<(), TandaError> {
+        if tanda.auto_start_when_full && tanda.active_members >= tanda.max_members {
+            Self::start_internal(env, tanda)?;
+        }
+
+        Ok(())
+    }
+
+    fn get_invite_codes_internal(env: &Env, tanda_id: &String) -> Map<BytesN<32>, InviteCode> {
+        env.storage().persistent()
+            .get(&DataKey::InviteCodes(tanda_id.clone()))
+            .unwrap_or(Map::new(env))
+    }
+
+    /// Check an invite code against the registered hashes, consuming it if single-use
+    fn redeem_invite_code(env: &Env, tanda_id: &String, code: &Bytes) -> Result<(), TandaError> {
+        let hash: BytesN<32> = env.crypto().sha256(code).into();
+        let mut codes = Self::get_invite_codes_internal(env, tanda_id);
+        let invite = codes.get(hash.clone()).ok_or(TandaError::InvalidInviteCode)?;
+
+        if invite.expires_at != 0 && invite.expires_at <= env.ledger().timestamp() {
+            return Err(TandaError::InviteExpired);
+        }
+
+        if invite.single_use {
+            codes.remove(hash);
+            env.storage().persistent().set(&DataKey::InviteCodes(tanda_id.clone()), &codes);
+        }
+
+        Ok(())
+    }
+
+    /// Register a new member, or a member's next slot, at the end of the payout order
+    fn add_slot(
+        env: &Env,
+        tanda: &Tanda,
+        address: &Address,
+        slot: u32,
+        shares: Map<Address, u32>,
+    ) -> Member {
+        let mut positions = Self::get_positions(env, &tanda.id);
+        let member = Member {
+            address: address.clone(),
+            slot,
+            status: MemberStatus::Active,
+            position: positions.len(),
+            has_deposited: false,
+            deposit_cycle: 0,
+            paid_this_cycle: 0,
+            joined_at: env.ledger().timestamp(),
+            commitment: tanda.amount,
+            shares,
+            shares_paid: Map::new(env),
+        };
+        Self::save_member(env, &tanda.id, &member);
+
+        positions.push_back(SlotId { address: address.clone(), slot });
+        env.storage().persistent().set(&DataKey::Positions(tanda.id.clone()), &positions);
+
+        if slot == 0 {
+            let mut list = Self::get_member_list(env, &tanda.id);
+            list.push_back(address.clone());
+            env.storage().persistent().set(&DataKey::MemberList(tanda.id.clone()), &list);
+        }
+
+        member
+    }
+
+    /// Every slot in the tanda (a shared slot is listed once, under its holder)
+    fn get_members_internal(env: &Env, tanda: &Tanda) -> Result<Vec<Member>, TandaError> {
+        let mut members = Vec::new(env);
+        for address in Self::get_member_list(env, &tanda.id).iter() {
+            for m in Self::get_slots(env, tanda, &address)?.iter() {
+                if m.address == address {
+                    members.push_back(m);
+                }
+            }
+        }
+        Ok(members)
+    }
+
+    fn deposit_count(env: &Env, tanda_id: &String, cycle: u32) -> u32 {
+        env.storage().persistent()
+            .get(&DataKey::Deposits(tanda_id.clone(), cycle))
+            .unwrap_or(0)
+    }
+
+    fn all_deposited_internal(env: &Env, tanda: &Tanda) -> bool {
+        Self::deposit_count(env, &tanda.id, tanda.current_cycle) >= tanda.active_members
+    }
+
+    /// Active slot at the position paid out in the current cycle
+    fn find_beneficiary(env: &Env, tanda: &Tanda) -> Result<Member, TandaError> {
+        let id = Self::get_positions(env, &tanda.id)
+            .get(tanda.current_cycle.wrapping_sub(1))
+            .ok_or(TandaError::BeneficiaryNotFound)?;
+        let member = Self::get_slot(env, tanda, &id)?;
+
+        if member.status != MemberStatus::Active {
+            return Err(TandaError::BeneficiaryNotFound);
+        }
+
+        Ok(member)
+    }
+
+    /// Draw this cycle's beneficiary among the active members not yet paid and
+    /// move them into the cycle's slot. Everyone before that slot has received.
+    fn draw_beneficiary(env: &Env, tanda: &Tanda) -> Result<(), TandaError> {
+        let slot = tanda.current_cycle - 1;
+        let positions = Self::get_positions(env, &tanda.id);
+
+        let mut candidates: Vec<u32> = Vec::new(env);
+        for i in slot..positions.len() {
+            let m = Self::get_slot(env, tanda, &positions.get(i).unwrap())?;
+            if m.status == MemberStatus::Active {
+                candidates.push_back(i);
+            }
+        }
+        if candidates.is_empty() {
+            return Err(TandaError::BeneficiaryNotFound);
+        }
+
+        let drawn: u64 = env.prng().gen_range(0..candidates.len() as u64);
+        let winner = candidates.get(drawn as u32).unwrap();
+        Self::swap_positions(env, tanda, slot, winner)?;
+
+        env.events().publish(
+            (Symbol::new(env, "lottery_drawn"), positions.get(winner).unwrap().address),
+            (tanda.id.clone(), tanda.current_cycle),
+        );
+
+        Ok(())
+    }
+
+    /// Whether the beneficiary is only picked when the cycle pays out
+    fn chosen_at_payout(tanda: &Tanda) -> bool {
+        matches!(tanda.payout_order, PayoutOrder::Lottery | PayoutOrder::Auction)
+    }
+
+    /// Check that `user` can bid on the current cycle of an auction tanda: an
+    /// active slot of their own that hasn't received. A shared slot's owners
+    /// can't bid its payout down on their own.
+    fn check_bidder(env: &Env, tanda: &Tanda, user: &Address) -> Result<(), TandaError> {
+        if tanda.status != TandaStatus::Active {
+            return Err(TandaError::NotActive);
+        }
+        if tanda.payout_order != PayoutOrder::Auction {
+            return Err(TandaError::NotAuction);
+        }
+
+        let slots = Self::get_slots(env, tanda, user)?;
+        if !slots.iter().any(|m| m.status == MemberStatus::Active && m.shares.is_empty()) {
+            if slots.iter().all(|m| m.status == MemberStatus::Expelled) {
+                return Err(TandaError::MemberExpelled);
+            }
+            return Err(TandaError::NotEligibleToBid);
+        }
+        Ok(())
+    }
+
+    /// Record an open (or revealed) bid on the current cycle
+    fn record_bid(env: &Env, tanda: &Tanda, user: &Address, discount: i128) -> Result<(), TandaError> {
+        if discount <= 0 || discount >= Self::full_pot(env, tanda) {
+            return Err(TandaError::InvalidBid);
+        }
+
+        let key = DataKey::Bids(tanda.id.clone(), tanda.current_cycle);
+        let mut bids: Map<Address, i128> = env.storage().persistent()
+            .get(&key)
+            .unwrap_or(Map::new(env));
+        bids.set(user.clone(), discount);
+        env.storage().persistent().set(&key, &bids);
+        Self::extend_tanda_ttl(env, tanda);
+
+        env.events().publish(
+            (Symbol::new(env, "bid_placed"), user.clone()),
+            (tanda.id.clone(), tanda.current_cycle, discount),
+        );
+
+        Ok(())
+    }
+
+    /// When the reveal window of the current cycle's sealed bids ends (None
+    /// while bidding is still open)
+    fn reveal_deadline(env: &Env, tanda: &Tanda) -> Option<u64> {
+        env.storage().persistent().get(&DataKey::RevealDeadline(tanda.id.clone(), tanda.current_cycle))
+    }
+
+    /// Whether the current cycle's sealed bids are settled: every commitment is
+    /// revealed, or the reveal window is over (unrevealed bids are ignored)
+    fn bids_revealed(env: &Env, tanda: &Tanda) -> bool {
+        if !tanda.sealed_bids {
+            return true;
+        }
+        let sealed: Map<Address, BytesN<32>> = env.storage().persistent()
+            .get(&DataKey::SealedBids(tanda.id.clone(), tanda.current_cycle))
+            .unwrap_or(Map::new(env));
+        if sealed.is_empty() {
+            return true;
+        }
+        match Self::reveal_deadline(env, tanda) {
+            Some(deadline) => env.ledger().timestamp() >= deadline,
+            None => false,
+        }
+    }
+
+    /// Settle the current cycle's auction: the highest bid among members still
+    /// waiting wins the cycle's slot, and the discount is paid out of escrow
+    /// as equal dividends to every other slot that contributed. Without bids
+    /// the slot keeps its member. Returns the dividends paid.
+    fn settle_auction(env: &Env, tanda: &Tanda) -> Result<i128, TandaError> {
+        let slot = tanda.current_cycle - 1;
+        let positions = Self::get_positions(env, &tanda.id);
+        let bids: Map<Address, i128> = env.storage().persistent()
+            .get(&DataKey::Bids(tanda.id.clone(), tanda.current_cycle))
+            .unwrap_or(Map::new(env));
+
+        // Highest bid wins; ties go to whoever is earlier in the payout order
+        let mut winner = slot;
+        let mut discount: i128 = 0;
+        for i in slot..positions.len() {
+            let id = positions.get(i).unwrap();
+            let bid = bids.get(id.address.clone()).unwrap_or(0);
+            if bid <= discount {
+                continue;
+            }
+            if Self::get_slot(env, tanda, &id)?.status == MemberStatus::Active {
+                winner = i;
+                discount = bid;
+            }
+        }
+        Self::swap_positions(env, tanda, slot, winner)?;
+
+        // A member's other slots earn a dividend like anyone else's
+        let mut contributors: Vec<Member> = Vec::new(env);
+        for (i, id) in positions.iter().enumerate() {
+            let m = Self::get_slot(env, tanda, &id)?;
+            if i as u32 != winner && m.status != MemberStatus::Expelled && m.paid_this_cycle > 0 {
+                contributors.push_back(m);
+            }
+        }
+        let winner = positions.get(winner).ok_or(TandaError::BeneficiaryNotFound)?.address;
+
+        // Any remainder of an uneven split stays with the winner
+        let escrow: i128 = env.storage().persistent()
+            .get(&DataKey::Escrow(tanda.id.clone()))
+            .unwrap_or(0);
+        let dividend = if contributors.is_empty() {
+            0
+        } else {
+            discount.min(escrow) / contributors.len() as i128
+        };
+        for m in contributors.iter() {
+            Self::adjust_escrow(env, &tanda.id, -dividend);
+            Self::pay_slot(env, tanda, &m, dividend)?;
+        }
+
+        let key = DataKey::AuctionResult(tanda.id.clone(), tanda.current_cycle);
+        env.storage().persistent().set(&key, &AuctionResult {
+            winner: winner.clone(),
+            discount,
+            dividend,
+            recipients: contributors.len(),
+        });
+        env.storage().persistent().extend_ttl(&key, TANDA_LIFETIME_THRESHOLD, TANDA_BUMP_AMOUNT);
+
+        env.events().publish(
+            (Symbol::new(env, "auction_won"), winner),
+            (tanda.id.clone(), tanda.current_cycle, discount, dividend),
+        );
+
+        Ok(dividend * contributors.len() as i128)
+    }
+
+    /// Check that two members can exchange positions, returning their records.
+    /// The order only means something for tandas that don't pick the
+    /// beneficiary at payout (or shuffle it at start), and only for members
+    /// still waiting for their payout.
+    fn check_swap(
+        env: &Env,
+        tanda: &Tanda,
+        from: &Address,
+        to: &Address,
+    ) -> Result<(Member, Member), TandaError> {
+        if from == to {
+            return Err(TandaError::SwapNotAllowed);
+        }
+
+        Ok((Self::check_tradable(env, tanda, from)?, Self::check_tradable(env, tanda, to)?))
+    }
+
+    /// Check that a member's position can change hands, returning the record
+    /// of their next slot to be paid
+    fn check_tradable(env: &Env, tanda: &Tanda, address: &Address) -> Result<Member, TandaError> {
+        if tanda.status != TandaStatus::Forming && tanda.status != TandaStatus::Active {
+            return Err(TandaError::NotActive);
+        }
+        if Self::chosen_at_payout(tanda) {
+            return Err(TandaError::SwapNotAllowed);
+        }
+        if tanda.status == TandaStatus::Forming && tanda.payout_order == PayoutOrder::Random {
+            return Err(TandaError::SwapNotAllowed);
+        }
+
+        // A shared slot would change hands for every co-owner at once
+        let member = Self::next_slot(env, tanda, address)?.ok_or(TandaError::SwapNotAllowed)?;
+        if !member.shares.is_empty() {
+            return Err(TandaError::SwapNotAllowed);
+        }
+        Ok(member)
+    }
+
+    fn get_listings_internal(env: &Env, tanda_id: &String) -> Map<Address, i128> {
+        env.storage().persistent()
+            .get(&DataKey::Listings(tanda_id.clone()))
+            .unwrap_or(Map::new(env))
+    }
+
+    /// A listing can be bought until its slot's cycle starts
+    fn listing_live(tanda: &Tanda, seller: &Member) -> bool {
+        tanda.status == TandaStatus::Forming || seller.position >= tanda.current_cycle
+    }
+
+    /// Drop the listings of two members whose positions just changed
+    fn drop_listings(env: &Env, tanda_id: &String, a: &Address, b: &Address) {
+        let mut listings = Self::get_listings_internal(env, tanda_id);
+        let had_a = listings.remove(a.clone()).is_some();
+        let had_b = listings.remove(b.clone()).is_some();
+        if had_a || had_b {
+            env.storage().persistent().set(&DataKey::Listings(tanda_id.clone()), &listings);
+        }
+    }
+
+    /// Exchange two slots in the payout order
+    fn swap_positions(env: &Env, tanda: &Tanda, a: u32, b: u32) -> Result<(), TandaError> {
+        if a == b {
+            return Ok(());
+        }
+
+        let mut positions = Self::get_positions(env, &tanda.id);
+        let first = positions.get(a).ok_or(TandaError::BeneficiaryNotFound)?;
+        let second = positions.get(b).ok_or(TandaError::BeneficiaryNotFound)?;
+
+        let mut m = Self::get_slot(env, tanda, &first)?;
+        m.position = b;
+        Self::save_member(env, &tanda.id, &m);
+        let mut m = Self::get_slot(env, tanda, &second)?;
+        m.position = a;
+        Self::save_member(env, &tanda.id, &m);
+
+        positions.set(a, second);
+        positions.set(b, first);
+        env.storage().persistent().set(&DataKey::Positions(tanda.id.clone()), &positions);
+
+        Self::store_cycle_scale(env, tanda)
+    }
+
+    fn get_contribution(env: &Env, tanda_id: &String, cycle: u32, member: &Address) -> i128 {
+        env.storage().persistent()
+            .get(&DataKey::Contribution(tanda_id.clone(), cycle, member.clone()))
+            .unwrap_or(0)
+    }
+
+    /// Adjust a per-tanda balance (escrow or safety fund) and the contract-wide total
+    fn adjust_balance(env: &Env, key: DataKey, delta: i128) {
+        let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage().persistent().set(&key, &(balance + delta));
+
+        Self::adjust_total_escrow(env, delta);
+    }
+
+    fn adjust_total_escrow(env: &Env, delta: i128) {
+        let total: i128 = env.storage().instance()
+            .get(&DataKey::TotalEscrow)
+            .unwrap_or(0);
+        env.storage().instance().set(&DataKey::TotalEscrow, &(total + delta));
+    }
+
+    fn adjust_escrow(env: &Env, tanda_id: &String, delta: i128) {
+        Self::adjust_balance(env, DataKey::Escrow(tanda_id.clone()), delta);
+    }
+
+    fn transfer_out(env: &Env, to: &Address, amount: i128) -> Result<(), TandaError> {
+        let eurc_token: Address = env.storage().instance()
+            .get(&DataKey::EurcToken)
+            .ok_or(TandaError::NotInitialized)?;
+
+        let token = TokenClient::new(env, &eurc_token);
+        token.transfer(&env.current_contract_address(), to, &amount);
+
+        Ok(())
+    }
+
+    /// Record a deposit already transferred to the contract
+    fn record_contribution(env: &Env, tanda_id: &String, cycle: u32, member: &Address, amount: i128) {
+        let key = DataKey::Contribution(tanda_id.clone(), cycle, member.clone());
+        let paid = Self::get_contribution(env, tanda_id, cycle, member);
+        env.storage().persistent().set(&key, &(paid + amount));
+        env.storage().persistent().extend_ttl(&key, TANDA_LIFETIME_THRESHOLD, TANDA_BUMP_AMOUNT);
+
+        Self::adjust_escrow(env, tanda_id, amount);
+    }
+
+    /// Send escrowed EURC out of the contract
+    fn release_escrow(
+        env: &Env,
+        tanda_id: &String,
+        to: &Address,
+        amount: i128,
+    ) -> Result<(), TandaError> {
+        if amount <= 0 {
+            return Ok(());
+        }
+
+        Self::transfer_out(env, to, amount)?;
+        Self::adjust_escrow(env, tanda_id, -amount);
+
+        Ok(())
+    }
+
+    fn log_fund_movement(env: &Env, tanda_id: &String, entry: FundEntry) {
+        let key = DataKey::SafetyFundHistory(tanda_id.clone());
+        let mut history: Vec<FundEntry> = env.storage().persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+
+        // Deposits within a cycle are folded into a single contribution entry
+        if entry.movement == FundMovement::Contribution {
+            if let Some(mut last) = history.last() {
+                if last.movement == FundMovement::Contribution && last.cycle == entry.cycle {
+                    last.amount += entry.amount;
+                    last.timestamp = entry.timestamp;
+                    history.set(history.len() - 1, last);
+                    env.storage().persistent().set(&key, &history);
+                    return;
+                }
+            }
+        }
+
+        history.push_back(entry);
+        env.storage().persistent().set(&key, &history);
+    }
+
+    /// Record a member's safety fund share already transferred to the contract
+    fn collect_safety_fund(env: &Env, tanda_id: &String, cycle: u32, member: &Address, amount: i128) {
+        let key = DataKey::SafetyFundShares(tanda_id.clone());
+        let mut shares: Map<Address, i128> = env.storage().persistent()
+            .get(&key)
+            .unwrap_or(Map::new(env));
+        let paid = shares.get(member.clone()).unwrap_or(0);
+        shares.set(member.clone(), paid + amount);
+        env.storage().persistent().set(&key, &shares);
+
+        Self::adjust_balance(env, DataKey::SafetyFund(tanda_id.clone()), amount);
+        Self::log_fund_movement(env, tanda_id, FundEntry {
+            cycle,
+            movement: FundMovement::Contribution,
+            account: None,
+            amount,
+            timestamp: env.ledger().timestamp(),
+        });
+    }
+
+    /// Pay up to `wanted` from the safety fund to `to`. Returns the amount paid.
+    fn draw_safety_fund(
+        env: &Env,
+        tanda_id: &String,
+        cycle: u32,
+        to: &Address,
+        wanted: i128,
+        movement: FundMovement,
+    ) -> Result<i128, TandaError> {
+        let fund: i128 = env.storage().persistent()
+            .get(&DataKey::SafetyFund(tanda_id.clone()))
+            .unwrap_or(0);
+        let amount = if wanted < fund { wanted } else { fund };
+        if amount <= 0 {
+            return Ok(0);
+        }
+
+        Self::transfer_out(env, to, amount)?;
+        Self::adjust_balance(env, DataKey::SafetyFund(tanda_id.clone()), -amount);
+        Self::log_fund_movement(env, tanda_id, FundEntry {
+            cycle,
+            movement,
+            account: Some(to.clone()),
+            amount,
+            timestamp: env.ledger().timestamp(),
+        });
+
+        Ok(amount)
+    }
+
+    /// Refund what is left of the safety fund to members in good standing,
+    /// pro-rata to what each of them paid into it (evenly if nobody paid in,
+    /// and the fund only holds forfeited payout shares)
+    fn settle_safety_fund(
+        env: &Env,
+        tanda_id: &String,
+        tanda: &Tanda,
+        members: &Vec<Member>,
+    ) -> Result<(), TandaError> {
+        let fund: i128 = env.storage().persistent()
+            .get(&DataKey::SafetyFund(tanda_id.clone()))
+            .unwrap_or(0);
+        if fund <= 0 {
+            return Ok(());
+        }
+
+        let shares: Map<Address, i128> = env.storage().persistent()
+            .get(&DataKey::SafetyFundShares(tanda_id.clone()))
+            .unwrap_or(Map::new(env));
+
+        // A member with several slots stays eligible while any of them is;
+        // a co-owner while they still have a share in theirs
+        let paid_in = shares.values().iter().any(|share| share > 0);
+        let mut eligible: Vec<(Address, i128)> = Vec::new(env);
+        let mut total_shares: i128 = 0;
+        for address in Self::get_member_list(env, tanda_id).iter() {
+            let share = if paid_in { shares.get(address.clone()).unwrap_or(0) } else { 1 };
+            let in_good_standing = members.iter().any(|m| {
+                m.status != MemberStatus::Expelled && Self::owners(env, &m).contains(&address)
+            });
+            if in_good_standing && share > 0 {
+                eligible.push_back((address, share));
+                total_shares += share;
+            }
+        }
+
+        // The last member also takes the rounding remainder
+        let mut remaining = fund;
+        for (i, (address, share)) in eligible.iter().enumerate() {
+            let refund = if i as u32 == eligible.len() - 1 {
+                remaining
+            } else {
+                fund * share / total_shares
+            };
+            remaining -= Self::draw_safety_fund(
+                env, tanda_id, tanda.current_cycle, &address, refund, FundMovement::Refund,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Refund what a member paid into a cycle beyond `keep` (what their
+    /// other slots still have in it) and drop it from the record
+    fn refund_contribution(
+        env: &Env,
+        tanda_id: &String,
+        cycle: u32,
+        member: &Address,
+        keep: i128,
+    ) -> Result<(), TandaError> {
+        let paid = Self::get_contribution(env, tanda_id, cycle, member);
+        let refund = paid - keep;
+        if refund <= 0 {
+            return Ok(());
+        }
+
+        let key = DataKey::Contribution(tanda_id.clone(), cycle, member.clone());
+        if keep > 0 {
+            env.storage().persistent().set(&key, &keep);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+
+        Self::release_escrow(env, tanda_id, member, refund)?;
+
+        env.events().publish(
+            (Symbol::new(env, "deposit_refunded"), member.clone()),
+            (tanda_id.clone(), cycle, refund),
+        );
+
+        Ok(())
+    }
+
+    fn get_collateral_internal(env: &Env, tanda_id: &String) -> Map<Address, i128> {
+        env.storage().persistent()
+            .get(&DataKey::Collateral(tanda_id.clone()))
+            .unwrap_or(Map::new(env))
+    }
+
+    /// Bond posted for each slot
+    fn slot_bond(tanda: &Tanda) -> i128 {
+        tanda.amount * tanda.collateral_multiple as i128
+    }
+
+    /// Escrow the bond for a new member or slot (no-op for tandas without collateral)
+    fn post_collateral(
+        env: &Env,
+        tanda: &Tanda,
+        member: &Address,
+        bond: i128,
+    ) -> Result<(), TandaError> {
+        if bond <= 0 {
+            return Ok(());
+        }
+
+        let eurc_token: Address = env.storage().instance()
+            .get(&DataKey::EurcToken)
+            .ok_or(TandaError::NotInitialized)?;
+        let token = TokenClient::new(env, &eurc_token);
+        token.transfer(member, &env.current_contract_address(), &bond);
+        Self::adjust_total_escrow(env, bond);
+
+        Self::hold_collateral(env, &tanda.id, member, bond);
+
+        Ok(())
+    }
+
+    /// Add a bond already escrowed by the contract to a member's collateral
+    fn hold_collateral(env: &Env, tanda_id: &String, member: &Address, bond: i128) {
+        let mut collateral = Self::get_collateral_internal(env, tanda_id);
+        let held = collateral.get(member.clone()).unwrap_or(0);
+        collateral.set(member.clone(), held + bond);
+        env.storage().persistent().set(&DataKey::Collateral(tanda_id.clone()), &collateral);
+
+        env.events().publish(
+            (Symbol::new(env, "collateral_posted"), member.clone()),
+            (tanda_id.clone(), bond),
+        );
+    }
+
+    fn get_join_requests_internal(env: &Env, tanda_id: &String) -> Vec<JoinRequest> {
+        env.storage().persistent()
+            .get(&DataKey::JoinRequests(tanda_id.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// File a join request, escrowing the bond the member would post on joining
+    fn request_to_join(env: &Env, tanda: &Tanda, user: &Address) -> Result<(), TandaError> {
+        Self::prune_join_requests(env, &tanda.id)?;
+
+        let mut requests = Self::get_join_requests_internal(env, &tanda.id);
+        if requests.iter().any(|r| r.address == *user) {
+            return Err(TandaError::RequestPending);
+        }
+
+        let bond = tanda.amount * tanda.collateral_multiple as i128;
+        if bond > 0 {
+            let eurc_token: Address = env.storage().instance()
+                .get(&DataKey::EurcToken)
+                .ok_or(TandaError::NotInitialized)?;
+            let token = TokenClient::new(env, &eurc_token);
+            token.transfer(user, &env.current_contract_address(), &bond);
+            Self::adjust_total_escrow(env, bond);
+        }
+
+        let now = env.ledger().timestamp();
+        requests.push_back(JoinRequest {
+            address: user.clone(),
+            requested_at: now,
+            expires_at: now + tanda.request_expiry_days * SECONDS_PER_DAY,
+            bond,
+        });
+        env.storage().persistent().set(&DataKey::JoinRequests(tanda.id.clone()), &requests);
+        Self::extend_tanda_ttl(env, tanda);
+
+        env.events().publish(
+            (Symbol::new(env, "join_requested"), user.clone()),
+            tanda.id.clone(),
+        );
+
+        Ok(())
+    }
+
+    /// Remove a member's join request (expired or not) and return it
+    fn take_join_request(
+        env: &Env,
+        tanda_id: &String,
+        member: &Address,
+    ) -> Result<JoinRequest, TandaError> {
+        let mut requests = Self::get_join_requests_internal(env, tanda_id);
+        let i = requests.iter()
+            .position(|r| r.address == *member)
+            .ok_or(TandaError::RequestNotFound)?;
+
+        let request = requests.get(i as u32).unwrap();
+        requests.remove(i as u32);
+        env.storage().persistent().set(&DataKey::JoinRequests(tanda_id.clone()), &requests);
+
+        Ok(request)
+    }
+
+    /// Return the bond escrowed with a join request that was not approved
+    fn release_request_bond(env: &Env, request: &JoinRequest) -> Result<(), TandaError> {
+        if request.bond <= 0 {
+            return Ok(());
+        }
+
+        Self::transfer_out(env, &request.address, request.bond)?;
+        Self::adjust_total_escrow(env, -request.bond);
+
+        Ok(())
+    }
+
+    /// Drop expired join requests, returning their bonds
+    fn prune_join_requests(env: &Env, tanda_id: &String) -> Result<(), TandaError> {
+        let now = env.ledger().timestamp();
+        let requests = Self::get_join_requests_internal(env, tanda_id);
+
+        let mut pending = Vec::new(env);
+        for request in requests.iter() {
+            if request.expires_at > now {
+                pending.push_back(request);
+            } else {
+                Self::release_request_bond(env, &request)?;
+            }
+        }
+
+        if pending.len() != requests.len() {
+            env.storage().persistent().set(&DataKey::JoinRequests(tanda_id.clone()), &pending);
+        }
+
+        Ok(())
+    }
+
+    /// Drop every join request once the tanda stops forming, returning their bonds
+    fn clear_join_requests(env: &Env, tanda_id: &String) -> Result<(), TandaError> {
+        let key = DataKey::JoinRequests(tanda_id.clone());
+        if !env.storage().persistent().has(&key) {
+            return Ok(());
+        }
+
+        for request in Self::get_join_requests_internal(env, tanda_id).iter() {
+            Self::release_request_bond(env, &request)?;
+        }
+        env.storage().persistent().remove(&key);
+
+        Ok(())
+    }
+
+    /// Remove up to `up_to` of a member's bond from escrow (one slot's bond,
+    /// or `i128::MAX` for all of it), returning the amount taken
+    fn take_collateral(env: &Env, tanda_id: &String, member: &Address, up_to: i128) -> i128 {
+        let mut collateral = Self::get_collateral_internal(env, tanda_id);
+        let held = collateral.get(member.clone()).unwrap_or(0);
+        let bond = held.min(up_to);
+        if bond <= 0 {
+            return 0;
+        }
+
+        if held > bond {
+            collateral.set(member.clone(), held - bond);
+        } else {
+            collateral.remove(member.clone());
+        }
+        env.storage().persistent().set(&DataKey::Collateral(tanda_id.clone()), &collateral);
+        Self::adjust_total_escrow(env, -bond);
+        bond
+    }
+
+    fn return_collateral(
+        env: &Env,
+        tanda_id: &String,
+        member: &Address,
+        up_to: i128,
+    ) -> Result<(), TandaError> {
+        let bond = Self::take_collateral(env, tanda_id, member, up_to);
+        if bond <= 0 {
+            return Ok(());
+        }
+
+        Self::transfer_out(env, member, bond)?;
+
+        env.events().publish(
+            (Symbol::new(env, "collateral_returned"), member.clone()),
+            (tanda_id.clone(), bond),
+        );
+
+        Ok(())
+    }
+
+    /// Share up to `up_to` of a defaulter's bond among the slots still in the tanda
+    fn slash_collateral(
+        env: &Env,
+        tanda_id: &String,
+        tanda: &Tanda,
+        defaulter: &Address,
+        up_to: i128,
+    ) -> Result<(), TandaError> {
+        let bond = Self::take_collateral(env, tanda_id, defaulter, up_to);
+        if bond <= 0 {
+            return Ok(());
+        }
+
+        let mut remaining_members: Vec<Member> = Vec::new(env);
+        for id in Self::get_positions(env, tanda_id).iter() {
+            let m = Self::get_slot(env, tanda, &id)?;
+            if m.status != MemberStatus::Expelled {
+                remaining_members.push_back(m);
+            }
+        }
+
+        // The last member also takes the rounding remainder
+        let count = remaining_members.len();
+        let share = bond / count as i128;
+        for (i, m) in remaining_members.iter().enumerate() {
+            let amount = if i as u32 == count - 1 {
+                bond - share * (count as i128 - 1)
+            } else {
+                share
+            };
+            Self::pay_slot(env, tanda, &m, amount)?;
+        }
+
+        env.events().publish(
+            (Symbol::new(env, "collateral_slashed"), defaulter.clone()),
+            (tanda_id.clone(), bond),
+        );
+
+        Ok(())
+    }
+
+    /// Mark the tanda completed and settle everything it still holds for members
+    fn complete_tanda(env: &Env, tanda_id: &String, tanda: &mut Tanda) -> Result<(), TandaError> {
+        tanda.status = TandaStatus::Completed;
+
+        let members = Self::get_members_internal(env, tanda)?;
+        Self::settle_safety_fund(env, tanda_id, tanda, &members)?;
+
+        for m in members.iter() {
+            for owner in Self::owners(env, &m).iter() {
+                if m.status != MemberStatus::Expelled {
+                    Self::return_collateral(env, tanda_id, &owner, i128::MAX)?;
+                }
+                Self::refund_prepayment(env, tanda, &owner)?;
+            }
+        }
+        Self::clear_waitlist(env, tanda_id)?;
+
+        Ok(())
+    }
+
+    /// Pay the current cycle's contributions to the beneficiary and move to the next cycle
+    fn pay_beneficiary(env: &Env, tanda_id: &String, tanda: &mut Tanda) -> Result<(), TandaError> {
+        let dividends = match tanda.payout_order {
+            PayoutOrder::Lottery => {
+                Self::draw_beneficiary(env, tanda)?;
+                0
+            }
+            PayoutOrder::Auction => Self::settle_auction(env, tanda)?,
+            _ => 0,
+        };
+        let mut member = Self::find_beneficiary(env, tanda)?;
+        let recipient = member.address.clone();
+
+        // Pay out what was collected this cycle (the escrow only ever holds the open cycle)
+        let mut payout: i128 = env.storage().persistent()
+            .get(&DataKey::Escrow(tanda_id.clone()))
+            .unwrap_or(0);
+
+        Self::adjust_escrow(env, tanda_id, -payout);
+        Self::pay_slot(env, tanda, &member, payout)?;
+
+        // Members who defaulted after receiving leave a hole in the pot;
+        // the safety fund covers it so the beneficiary still gets the full amount
+        let full_pot = Self::full_pot(env, tanda) - dividends;
+        if payout < full_pot {
+            for (owner, part) in Self::split_by_share(env, &member, full_pot - payout).iter() {
+                payout += Self::draw_safety_fund(
+                    env, tanda_id, tanda.current_cycle, &owner, part, FundMovement::TopUp,
+                )?;
+            }
+        }
+
+        // Mark recipient's slot as Received (deposit flags reset with the new cycle)
+        member.status = MemberStatus::Received;
+        Self::save_member(env, tanda_id, &member);
+
+        // Advance cycle
+        tanda.current_cycle += 1;
+        tanda.last_payout_at = env.ledger().timestamp();
+        Self::store_cycle_scale(env, tanda)?;
+
+        // Check if tanda is complete; otherwise prepaid members are in already
+        if tanda.current_cycle > tanda.total_cycles {
+            Self::complete_tanda(env, tanda_id, tanda)?;
+        } else {
+            Self::apply_prepayments(env, tanda)?;
+        }
+
+        env.events().publish(
+            (Symbol::new(env, "payout_sent"), recipient),
+            (tanda_id.clone(), payout),
+        );
+
+        Ok(())
+    }
+
+    /// Mark a member's slot as expelled, refund anything paid into the open
+    /// cycle for it and close the gap it leaves in the payout order
+    fn expel_member(
+        env: &Env,
+        tanda_id: &String,
+        tanda: &mut Tanda,
+        member: &mut Member,
+    ) -> Result<(), TandaError> {
+        let had_received = member.status == MemberStatus::Received;
+
+        // A shared slot some owner paid for stays in; only the others go
+        if Self::drop_defaulters(env, tanda_id, tanda, member)? {
+            return Ok(());
+        }
+
+        // Installments toward the member's other slots stay in the pot
+        let mut keep: i128 = 0;
+        for other in Self::get_slots(env, tanda, &member.address)?.iter() {
+            if other.slot != member.slot && other.status != MemberStatus::Expelled {
+                keep += other.paid_this_cycle;
+            }
+        }
+
+        member.status = MemberStatus::Expelled;
+        Self::save_member(env, tanda_id, member);
+        tanda.active_members -= 1;
+
+        // Defaulting after taking a payout costs the slot's bond; otherwise it
+        // is returned. Co-owners of a shared slot each posted their part of it.
+        let bond = if member.shares.is_empty() { Self::slot_bond(tanda) } else { i128::MAX };
+        for owner in Self::owners(env, member).iter() {
+            Self::refund_contribution(env, tanda_id, tanda.current_cycle, &owner, keep)?;
+            Self::refund_prepayment(env, tanda, &owner)?;
+            if had_received {
+                Self::slash_collateral(env, tanda_id, tanda, &owner, bond)?;
+            } else {
+                Self::return_collateral(env, tanda_id, &owner, bond)?;
+            }
+        }
+
+        env.events().publish(
+            (Symbol::new(env, "member_expelled"), member.address.clone()),
+            tanda_id.clone(),
+        );
+
+        // A member who already received keeps their past slot. Otherwise the
+        // first in the waitlist takes it over, or the rotation loses one cycle
+        // and everyone behind them moves up.
+        if !had_received && !Self::replace_member(env, tanda, member)? {
+            tanda.total_cycles -= 1;
+            Self::remove_position(env, tanda, member.position)?;
+        }
+
+        Ok(())
+    }
+
+    /// Drop the co-owners of a shared slot who haven't paid their share, if
+    /// another owner has: the slot stays in with the shares left and counts as
+    /// deposited. What the dropped owners paid is refunded and their bond is
+    /// returned, or shared out if the slot already received. Returns whether
+    /// the slot was kept.
+    ///
+    /// From then on the slot only pays in the shares left, so every pot it
+    /// deposits into is short by the dropped share. `pay_beneficiary` tops
+    /// that up from the safety fund (which also takes the dropped share of the
+    /// slot's own payout); whatever the fund can't cover, beneficiaries lose.
+    fn drop_defaulters(
+        env: &Env,
+        tanda_id: &String,
+        tanda: &Tanda,
+        member: &mut Member,
+    ) -> Result<bool, TandaError> {
+        if !Self::partly_paid(env, tanda, member) {
+            return Ok(false);
+        }
+
+        let mut defaulters: Vec<Address> = Vec::new(env);
+        for owner in member.shares.keys().iter() {
+            if Self::owed_by(env, tanda, member, &owner) != Some(0) {
+                defaulters.push_back(owner.clone());
+                member.paid_this_cycle -= member.shares_paid.get(owner.clone()).unwrap_or(0);
+                member.shares.remove(owner.clone());
+                member.shares_paid.remove(owner);
+            }
+        }
+        member.has_deposited = true;
+        Self::save_member(env, tanda_id, member);
+
+        let count = Self::deposit_count(env, tanda_id, tanda.current_cycle);
+        let deposits_key = DataKey::Deposits(tanda_id.clone(), tanda.current_cycle);
+        env.storage().persistent().set(&deposits_key, &(count + 1));
+
+        for owner in defaulters.iter() {
+            Self::refund_contribution(env, tanda_id, tanda.current_cycle, &owner, 0)?;
+            if member.status == MemberStatus::Received {
+                Self::slash_collateral(env, tanda_id, tanda, &owner, i128::MAX)?;
+            } else {
+                Self::return_collateral(env, tanda_id, &owner, i128::MAX)?;
+            }
+
+            env.events().publish(
+                (Symbol::new(env, "co_owner_dropped"), owner),
+                (tanda_id.clone(), member.address.clone()),
+            );
+        }
+
+        Ok(true)
+    }
+
+    /// Put the first waitlisted address in the slot of a member expelled
+    /// before receiving. Out of what they escrowed, the replacement pays the
+    /// cycles already completed to the expelled member (who paid them in),
+    /// deposits for the open cycle (with commission and safety fund share)
+    /// and posts any collateral; the rest is returned. Returns false if
+    /// nobody is waiting.
+    fn replace_member(env: &Env, tanda: &mut Tanda, expelled: &Member) -> Result<bool, TandaError> {
+        let mut waitlist: Vec<WaitlistEntry> = env.storage().persistent()
+            .get(&DataKey::Waitlist(tanda.id.clone()))
+            .unwrap_or(Vec::new(env));
+        let Some(entry) = waitlist.pop_front() else {
+            return Ok(false);
+        };
+        env.storage().persistent().set(&DataKey::Waitlist(tanda.id.clone()), &waitlist);
+
+        let replacement = Member {
+            address: entry.address.clone(),
+            slot: 0,
+            status: MemberStatus::Active,
+            position: expelled.position,
+            has_deposited: false,
+            deposit_cycle: tanda.current_cycle,
+            paid_this_cycle: 0,
+            joined_at: env.ledger().timestamp(),
+            commitment: expelled.commitment,
+            shares: Map::new(env),
+            shares_paid: Map::new(env),
+        };
+        Self::save_member(env, &tanda.id, &replacement);
+
+        let mut positions = Self::get_positions(env, &tanda.id);
+        positions.set(expelled.position, SlotId { address: entry.address.clone(), slot: 0 });
+        env.storage().persistent().set(&DataKey::Positions(tanda.id.clone()), &positions);
+
+        let mut list = Self::get_member_list(env, &tanda.id);
+        list.push_back(entry.address.clone());
+        env.storage().persistent().set(&DataKey::MemberList(tanda.id.clone()), &list);
+        tanda.active_members += 1;
+
+        // Everything leaves the waitlist escrow; the open cycle's deposit is
+        // counted again as it goes into the cycle's escrow
+        let catch_up = tanda.amount * (tanda.current_cycle - 1) as i128;
+        let bond = Self::slot_bond(tanda);
+        let deposit = Self::prepaid_cycle_cost(tanda, tanda.amount);
+        let refund = entry.held - catch_up - deposit - entry.commission - bond;
+        Self::adjust_total_escrow(env, -(entry.held - bond));
+
+        Self::pay_slot(env, tanda, expelled, catch_up)?;
+        if entry.commission > 0 {
+            Self::transfer_out(env, &Self::get_commission_address(env)?, entry.commission)?;
+        }
+        Self::credit_deposit(
+            env, tanda, &entry.address, replacement, tanda.amount, deposit - tanda.amount,
+        );
+        if bond > 0 {
+            Self::hold_collateral(env, &tanda.id, &entry.address, bond);
+        }
+        if refund > 0 {
+            Self::transfer_out(env, &entry.address, refund)?;
+        }
+
+        env.events().publish(
+            (Symbol::new(env, "member_replaced"), expelled.address.clone(), entry.address),
+            (tanda.id.clone(), expelled.position, catch_up),
+        );
+
+        Ok(true)
+    }
+
+    /// Return everything escrowed by people still on the waitlist
+    fn clear_waitlist(env: &Env, tanda_id: &String) -> Result<(), TandaError> {
+        let key = DataKey::Waitlist(tanda_id.clone());
+        let waitlist: Vec<WaitlistEntry> = env.storage().persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+
+        for entry in waitlist.iter() {
+            Self::transfer_out(env, &entry.address, entry.held)?;
+            Self::adjust_total_escrow(env, -entry.held);
+        }
+        env.storage().persistent().remove(&key);
+
+        Ok(())
+    }
+
+    /// Drop a payout position; everyone behind it moves up one place
+    fn remove_position(env: &Env, tanda: &Tanda, position: u32) -> Result<(), TandaError> {
+        let mut positions = Self::get_positions(env, &tanda.id);
+        positions.remove(position);
+
+        for i in position..positions.len() {
+            let mut m = Self::get_slot(env, tanda, &positions.get(i).unwrap())?;
+            m.position = i;
+            Self::save_member(env, &tanda.id, &m);
+        }
+
+        env.storage().persistent().set(&DataKey::Positions(tanda.id.clone()), &positions);
+
+        Self::store_cycle_scale(env, tanda)
+    }
+
+    /// Rewrite the payout order, updating the position of every slot that moved
+    fn set_positions(env: &Env, tanda: &Tanda, positions: &Vec<SlotId>) -> Result<(), TandaError> {
+        for (i, id) in positions.iter().enumerate() {
+            let mut m = Self::get_slot(env, tanda, &id)?;
+            if m.position != i as u32 {
+                m.position = i as u32;
+                Self::save_member(env, &tanda.id, &m);
+            }
+        }
+
+        env.storage().persistent().set(&DataKey::Positions(tanda.id.clone()), positions);
+
+        Self::store_cycle_scale(env, tanda)
+    }
+
+    /// Complete the tanda if expulsions left nobody to pay, refunding the
+    /// deposits already made for the open cycle. Returns true if it closed.
+    fn close_if_exhausted(env: &Env, tanda_id: &String, tanda: &mut Tanda) -> Result<bool, TandaError> {
+        if tanda.active_members > 1 && tanda.current_cycle <= tanda.total_cycles {
+            return Ok(false);
+        }
+
+        for member in Self::get_member_list(env, tanda_id).iter() {
+            Self::refund_contribution(env, tanda_id, tanda.current_cycle, &member, 0)?;
+        }
+
+        // Late fees meant for a beneficiary nobody will be go to the safety fund
+        let fees: i128 = env.storage().persistent()
+            .get(&DataKey::Escrow(tanda_id.clone()))
+            .unwrap_or(0);
+        if fees > 0 {
+            Self::adjust_escrow(env, tanda_id, -fees);
+            Self::adjust_balance(env, DataKey::SafetyFund(tanda_id.clone()), fees);
+            Self::log_fund_movement(env, tanda_id, FundEntry {
+                cycle: tanda.current_cycle,
+                movement: FundMovement::LateFee,
+                account: None,
+                amount: fees,
+                timestamp: env.ledger().timestamp(),
+            });
+        }
+
+        Self::complete_tanda(env, tanda_id, tanda)?;
+        Ok(true)
+    }
+
+    /// Take the next tanda ID from the counter
+    fn next_tanda_id(env: &Env) -> String {
+        let count: u32 = env.storage().instance()
+            .get(&DataKey::TandaCount)
+            .unwrap_or(0);
+        let new_count = count + 1;
+        env.storage().instance().set(&DataKey::TandaCount, &new_count);
+
+        Self::generate_id(env, new_count)
+    }
+
+    fn generate_id(env: &Env, count: u32) -> String {
+        // Simple numeric ID
+        let mut chars = [b'0'; 8];
+        let mut n = count;
+        for i in 0..8 {
+            chars[7 - i] = b'0' + (n % 10) as u8;
+            n /= 10;
+        }
+        String::from_str(env, core::str::from_utf8(&chars).unwrap())
+    }
+}
+
+// ==================== TESTS ====================
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::testutils::{storage::Persistent as _, Address as _, Ledger, LedgerInfo};
+    use soroban_sdk::token::StellarAssetClient;
+
+    fn setup_env() -> (Env, Address, Address, Address) {
         let env = Env::default();
         env.mock_all_auths();
 
-        let admin = Address::generate(&env);
-        let eurc = env.register_stellar_asset_contract_v2(admin.clone()).address();
-        let commission = Address::generate(&env);
+        // Network-like TTL limits so tests catch entries that would be archived
+        env.ledger().set(LedgerInfo {
+            timestamp: 1_700_000_000,
+            protocol_version: 21,
+            sequence_number: 100,
+            network_id: Default::default(),
+            base_reserve: 10,
+            min_temp_entry_ttl: 16,
+            min_persistent_entry_ttl: 4096,
+            max_entry_ttl: 3_110_400,
+        });
+
+        let admin = Address::generate(&env);
+        let eurc = env.register_stellar_asset_contract_v2(admin.clone()).address();
+        let commission = Address::generate(&env);
+
+        (env, admin, eurc, commission)
+    }
+
+    fn mint(env: &Env, eurc: &Address, to: &Address, amount: i128) {
+        StellarAssetClient::new(env, eurc).mint(to, &amount);
+    }
+
+    fn advance_days(env: &Env, days: u64) {
+        env.ledger().with_mut(|li| {
+            li.timestamp += days * 86400;
+            li.sequence_number += days as u32 * DAY_IN_LEDGERS;
+        });
+    }
+
+    // The token's own instance is kept alive by network-wide traffic, not by us
+    fn keep_token_alive(env: &Env, eurc: &Address) {
+        env.as_contract(eurc, || {
+            env.storage().instance().extend_ttl(30 * DAY_IN_LEDGERS, 30 * DAY_IN_LEDGERS);
+        });
+    }
+
+    #[test]
+    fn test_create_and_start_tanda() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let name = String::from_str(&env, "Test Tanda");
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+
+        // Create tanda
+        let tanda_id = client.create_tanda(&creator, &name, &100_0000000, &5);
+
+        // Join
+        client.join_tanda(&member2, &tanda_id);
+
+        // Start
+        client.start_tanda(&creator, &tanda_id);
+
+        let tanda = client.get_tanda(&tanda_id);
+        assert_eq!(tanda.status, TandaStatus::Active);
+        assert_eq!(tanda.current_cycle, 1);
+        assert_eq!(tanda.total_cycles, 2);
+    }
+
+    #[test]
+    fn test_deposit_and_payout() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let name = String::from_str(&env, "Test Tanda");
+
+        // Setup tanda
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        let tanda_id = client.create_tanda(&creator, &name, &100_0000000, &5);
+        client.join_tanda(&member2, &tanda_id);
+        client.start_tanda(&creator, &tanda_id);
+
+        // Mint tokens and deposit
+        mint(&env, &eurc, &creator, 200_0000000);
+        mint(&env, &eurc, &member2, 200_0000000);
+
+        client.deposit(&creator, &tanda_id);
+        client.deposit(&member2, &tanda_id);
+
+        // Check all deposited
+        assert!(client.all_deposited(&tanda_id));
+
+        // Trigger payout
+        client.trigger_payout(&tanda_id);
+
+        let tanda = client.get_tanda(&tanda_id);
+        assert_eq!(tanda.current_cycle, 2);
+    }
+
+    #[test]
+    fn test_expel_delinquent() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let creator = Address::generate(&env);
+        let delinquent = Address::generate(&env);
+        let name = String::from_str(&env, "Test Tanda");
+
+        // Setup
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        let tanda_id = client.create_tanda(&creator, &name, &100_0000000, &5);
+        client.join_tanda(&delinquent, &tanda_id);
+        client.start_tanda(&creator, &tanda_id);
+
+        // Only creator deposits
+        mint(&env, &eurc, &creator, 200_0000000);
+        client.deposit(&creator, &tanda_id);
+
+        // Advance time 7 days
+        advance_days(&env, 7);
+
+        // Can expel
+        assert!(client.can_expel(&tanda_id, &delinquent));
+
+        // Expel
+        client.expel_delinquent(&tanda_id, &delinquent);
+
+        // Check expelled
+        let members = client.get_members(&tanda_id);
+        for m in members.iter() {
+            if m.address == delinquent {
+                assert_eq!(m.status, MemberStatus::Expelled);
+            }
+        }
+    }
+
+    #[test]
+    fn test_expelled_final_beneficiary_refunds_cycle() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let c = Address::generate(&env);
+        mint(&env, &eurc, &a, CREATE_TANDA_FEE);
+        for m in [&a, &b, &c] {
+            mint(&env, &eurc, m, 500_0000000);
+        }
+
+        let tanda_id = client.create_tanda(&a, &String::from_str(&env, "Escrow"), &100_0000000, &3);
+        client.join_tanda(&b, &tanda_id);
+        client.join_tanda(&c, &tanda_id);
+        client.start_tanda(&a, &tanda_id);
+
+        // Cycles 1 and 2 pay A and B
+        for _ in 0..2 {
+            client.deposit(&a, &tanda_id);
+            client.deposit(&b, &tanda_id);
+            client.deposit(&c, &tanda_id);
+            assert!(client.advance(&tanda_id));
+        }
+
+        // Cycle 3: C (the last beneficiary) stops paying
+        client.deposit(&a, &tanda_id);
+        client.deposit(&b, &tanda_id);
+        assert_eq!(client.get_escrow(&tanda_id), 200_0000000);
+
+        let a_before = token.balance(&a);
+        advance_days(&env, 7);
+        client.advance(&tanda_id);
+
+        // Nobody is left to receive, so A and B get their cycle-3 deposits back
+        let tanda = client.get_tanda(&tanda_id);
+        assert_eq!(tanda.status, TandaStatus::Completed);
+        assert_eq!(token.balance(&a), a_before + 100_0000000);
+        assert_eq!(client.get_escrow(&tanda_id), 0);
+        assert_eq!(client.get_unallocated_balance(), 0);
+        assert_eq!(token.balance(&contract_id), 0);
+    }
+
+    #[test]
+    fn test_expelled_receiver_keeps_rotation() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let c = Address::generate(&env);
+        mint(&env, &eurc, &a, CREATE_TANDA_FEE);
+        for m in [&a, &b, &c] {
+            mint(&env, &eurc, m, 500_0000000);
+        }
+
+        let tanda_id = client.create_tanda(&a, &String::from_str(&env, "Escrow"), &100_0000000, &3);
+        client.join_tanda(&b, &tanda_id);
+        client.join_tanda(&c, &tanda_id);
+        client.start_tanda(&a, &tanda_id);
+
+        // Cycle 1 pays A, who then disappears
+        client.deposit(&a, &tanda_id);
+        client.deposit(&b, &tanda_id);
+        client.deposit(&c, &tanda_id);
+        client.advance(&tanda_id);
+
+        client.deposit(&b, &tanda_id);
+        client.deposit(&c, &tanda_id);
+        advance_days(&env, 7);
+
+        // A is expelled and B still receives cycle 2
+        let b_before = token.balance(&b);
+        assert!(client.advance(&tanda_id));
+        assert_eq!(token.balance(&b), b_before + 200_0000000);
+        assert_eq!(client.get_beneficiary(&tanda_id), c);
+
+        client.deposit(&b, &tanda_id);
+        client.deposit(&c, &tanda_id);
+        client.advance(&tanda_id);
+
+        let tanda = client.get_tanda(&tanda_id);
+        assert_eq!(tanda.status, TandaStatus::Completed);
+        assert_eq!(client.get_contributions(&tanda_id, &3).len(), 2);
+        assert_eq!(client.get_unallocated_balance(), 0);
+    }
+
+    #[test]
+    fn test_safety_fund_tops_up_after_default() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &0);
+
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let c = Address::generate(&env);
+        mint(&env, &eurc, &a, CREATE_TANDA_FEE);
+        for m in [&a, &b, &c] {
+            mint(&env, &eurc, m, 500_0000000);
+        }
+
+        let mut config = client.default_config();
+        config.safety_fund_bps = 1000; // 10%
+        let name = String::from_str(&env, "Fund");
+        let tanda_id = client.create_tanda_with_config(&a, &name, &100_0000000, &3, &config);
+        client.join_tanda(&b, &tanda_id);
+        client.join_tanda(&c, &tanda_id);
+        client.start_tanda(&a, &tanda_id);
+
+        // Cycle 1: A receives, fund collects 3 x 10
+        client.deposit(&a, &tanda_id);
+        client.deposit(&b, &tanda_id);
+        client.deposit(&c, &tanda_id);
+        client.advance(&tanda_id);
+        assert_eq!(client.get_safety_fund(&tanda_id), 30_0000000);
+
+        // Cycle 2: A defaults after receiving; the fund covers what it can
+        client.deposit(&b, &tanda_id);
+        client.deposit(&c, &tanda_id);
+        advance_days(&env, 7);
+
+        let b_before = token.balance(&b);
+        client.advance(&tanda_id);
+        assert_eq!(token.balance(&b), b_before + 250_0000000);
+        assert_eq!(client.get_safety_fund(&tanda_id), 0);
+
+        let history = client.get_safety_fund_history(&tanda_id);
+        let top_up = history.last().unwrap();
+        assert_eq!(top_up.movement, FundMovement::TopUp);
+        assert_eq!(top_up.account, Some(b.clone()));
+        assert_eq!(top_up.amount, 50_0000000);
+        assert_eq!(client.get_unallocated_balance(), 0);
+    }
+
+    #[test]
+    fn test_safety_fund_refunded_at_completion() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &0);
+
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        mint(&env, &eurc, &a, CREATE_TANDA_FEE);
+        mint(&env, &eurc, &a, 220_0000000);
+        mint(&env, &eurc, &b, 220_0000000);
+
+        let mut config = client.default_config();
+        config.safety_fund_bps = 1000;
+        let name = String::from_str(&env, "Fund");
+        let tanda_id = client.create_tanda_with_config(&a, &name, &100_0000000, &2, &config);
+        client.join_tanda(&b, &tanda_id);
+        client.start_tanda(&a, &tanda_id);
+
+        for _ in 0..2 {
+            client.deposit(&a, &tanda_id);
+            client.deposit(&b, &tanda_id);
+            client.advance(&tanda_id);
+        }
+
+        // Nobody defaulted, so everyone gets their fund share back
+        assert_eq!(client.get_tanda(&tanda_id).status, TandaStatus::Completed);
+        assert_eq!(client.get_safety_fund(&tanda_id), 0);
+        assert_eq!(token.balance(&a), 220_0000000);
+        assert_eq!(token.balance(&b), 220_0000000);
+
+        let history = client.get_safety_fund_history(&tanda_id);
+        assert_eq!(history.len(), 4); // 2 cycle contributions + 2 refunds
+        assert_eq!(history.get(0).unwrap().amount, 20_0000000);
+        assert_eq!(history.get(3).unwrap().movement, FundMovement::Refund);
+    }
+
+    #[test]
+    fn test_collateral_slashed_on_default_after_receiving() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &0);
+
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let c = Address::generate(&env);
+        mint(&env, &eurc, &a, CREATE_TANDA_FEE);
+        for m in [&a, &b, &c] {
+            mint(&env, &eurc, m, 400_0000000);
+        }
+
+        let mut config = client.default_config();
+        config.collateral_multiple = 1;
+        let name = String::from_str(&env, "Bonded");
+        let tanda_id = client.create_tanda_with_config(&a, &name, &100_0000000, &3, &config);
+        client.join_tanda(&b, &tanda_id);
+        client.join_tanda(&c, &tanda_id);
+        client.start_tanda(&a, &tanda_id);
+        assert_eq!(client.get_collateral(&tanda_id).get(b.clone()), Some(100_0000000));
+
+        // Cycle 1: A takes the pot, then stops paying
+        client.deposit(&a, &tanda_id);
+        client.deposit(&b, &tanda_id);
+        client.deposit(&c, &tanda_id);
+        client.advance(&tanda_id);
+
+        client.deposit(&b, &tanda_id);
+        client.deposit(&c, &tanda_id);
+        advance_days(&env, 7);
+        client.advance(&tanda_id);
+
+        // A's bond was split between B and C
+        let collateral = client.get_collateral(&tanda_id);
+        assert_eq!(collateral.get(a.clone()), None);
+        assert_eq!(collateral.len(), 2);
+
+        client.deposit(&b, &tanda_id);
+        client.deposit(&c, &tanda_id);
+        client.advance(&tanda_id);
+
+        // B and C each paid 300 + 100 bond, received 200, got the bond back plus half of A's
+        assert_eq!(client.get_tanda(&tanda_id).status, TandaStatus::Completed);
+        assert_eq!(token.balance(&b), 350_0000000);
+        assert_eq!(token.balance(&c), 350_0000000);
+        assert_eq!(token.balance(&contract_id), 0);
+        assert_eq!(client.get_unallocated_balance(), 0);
+    }
+
+    #[test]
+    fn test_typed_errors() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+
+        let creator = Address::generate(&env);
+        let member2 = Address::generate(&env);
+        let outsider = Address::generate(&env);
+        let name = String::from_str(&env, "Errors");
+        mint(&env, &eurc, &creator, 200_0000000);
+        mint(&env, &eurc, &member2, 200_0000000);
+
+        assert_eq!(
+            client.try_create_tanda(&creator, &name, &100_0000000, &2),
+            Err(Ok(TandaError::NotInitialized))
+        );
+
+        client.initialize(&admin, &eurc, &commission, &50);
+        assert_eq!(
+            client.try_initialize(&admin, &eurc, &commission, &50),
+            Err(Ok(TandaError::AlreadyInitialized))
+        );
+        assert_eq!(
+            client.try_create_tanda(&creator, &name, &0, &2),
+            Err(Ok(TandaError::InvalidAmount))
+        );
+        assert_eq!(
+            client.try_get_tanda(&String::from_str(&env, "99999999")).err(),
+            Some(Ok(TandaError::TandaNotFound))
+        );
+
+        let tanda_id = client.create_tanda(&creator, &name, &100_0000000, &2);
+        assert_eq!(
+            client.try_start_tanda(&creator, &tanda_id),
+            Err(Ok(TandaError::NotEnoughMembers))
+        );
+        assert_eq!(
+            client.try_deposit(&creator, &tanda_id),
+            Err(Ok(TandaError::NotActive))
+        );
+
+        client.join_tanda(&member2, &tanda_id);
+        assert_eq!(
+            client.try_join_tanda(&member2, &tanda_id),
+            Err(Ok(TandaError::TandaFull))
+        );
+        assert_eq!(
+            client.try_start_tanda(&member2, &tanda_id),
+            Err(Ok(TandaError::NotCreator))
+        );
+
+        client.start_tanda(&creator, &tanda_id);
+        assert_eq!(
+            client.try_join_tanda(&outsider, &tanda_id),
+            Err(Ok(TandaError::NotForming))
+        );
+        assert_eq!(
+            client.try_deposit(&outsider, &tanda_id),
+            Err(Ok(TandaError::NotMember))
+        );
+
+        client.deposit(&creator, &tanda_id);
+        assert_eq!(
+            client.try_deposit(&creator, &tanda_id),
+            Err(Ok(TandaError::AlreadyDeposited))
+        );
+        assert_eq!(
+            client.try_trigger_payout(&tanda_id),
+            Err(Ok(TandaError::NotAllDeposited))
+        );
+        assert_eq!(
+            client.try_expel_delinquent(&tanda_id, &member2),
+            Err(Ok(TandaError::DelinquencyNotPassed))
+        );
+
+        advance_days(&env, 7);
+        assert_eq!(
+            client.try_expel_delinquent(&tanda_id, &creator),
+            Err(Ok(TandaError::MemberHasDeposited))
+        );
+    }
+
+    #[test]
+    fn test_already_member_error() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let creator = Address::generate(&env);
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        let tanda_id = client.create_tanda(&creator, &String::from_str(&env, "Errors"), &100_0000000, &5);
+
+        assert_eq!(
+            client.try_join_tanda(&creator, &tanda_id),
+            Err(Ok(TandaError::AlreadyMember))
+        );
+    }
+
+    #[test]
+    fn test_leave_tanda_compacts_positions() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &0);
+
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let c = Address::generate(&env);
+        let d = Address::generate(&env);
+        mint(&env, &eurc, &a, CREATE_TANDA_FEE);
+        for m in [&a, &b, &c, &d] {
+            mint(&env, &eurc, m, 100_0000000);
+        }
+
+        let mut config = client.default_config();
+        config.collateral_multiple = 1;
+        let name = String::from_str(&env, "Leavers");
+        let tanda_id = client.create_tanda_with_config(&a, &name, &100_0000000, &3, &config);
+        client.join_tanda(&b, &tanda_id);
+        client.join_tanda(&c, &tanda_id);
+        assert_eq!(client.try_join_tanda(&d, &tanda_id), Err(Ok(TandaError::TandaFull)));
+
+        // B leaves: their bond comes back, C moves up and the seat frees up
+        client.leave_tanda(&b, &tanda_id);
+        assert_eq!(token.balance(&b), 100_0000000);
+        client.join_tanda(&d, &tanda_id);
+
+        let members = client.get_members(&tanda_id);
+        assert_eq!(members.len(), 3);
+        for (m, position) in [(&a, 0), (&c, 1), (&d, 2)] {
+            let member = members.iter().find(|x| x.address == *m).unwrap();
+            assert_eq!(member.position, position);
+        }
+        assert_eq!(client.try_leave_tanda(&b, &tanda_id), Err(Ok(TandaError::NotMember)));
+
+        // The creator leaves: C inherits the role and can start the tanda
+        client.leave_tanda(&a, &tanda_id);
+        let tanda = client.get_tanda(&tanda_id);
+        assert_eq!(tanda.creator, c);
+        assert_eq!(tanda.active_members, 2);
+        client.start_tanda(&c, &tanda_id);
+
+        assert_eq!(client.get_beneficiary(&tanda_id), c);
+        assert_eq!(client.try_leave_tanda(&d, &tanda_id), Err(Ok(TandaError::NotForming)));
+    }
+
+    #[test]
+    fn test_sole_creator_leaving_cancels_tanda() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let creator = Address::generate(&env);
+        let name = String::from_str(&env, "Lonely");
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        let tanda_id = client.create_tanda(&creator, &name, &100_0000000, &5);
+
+        client.leave_tanda(&creator, &tanda_id);
+
+        let tanda = client.get_tanda(&tanda_id);
+        assert_eq!(tanda.status, TandaStatus::Cancelled);
+        assert_eq!(client.get_members(&tanda_id).len(), 0);
+    }
+
+    #[test]
+    fn test_approval_required_join_flow() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &0);
+
+        let creator = Address::generate(&env);
+        let friend = Address::generate(&env);
+        let stranger = Address::generate(&env);
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        for m in [&creator, &friend, &stranger] {
+            mint(&env, &eurc, m, 100_0000000);
+        }
+
+        let mut config = client.default_config();
+        config.visibility = Visibility::ApprovalRequired;
+        config.collateral_multiple = 1;
+        let name = String::from_str(&env, "Private");
+        let tanda_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &5, &config);
+
+        // Joining files a request and escrows the bond with it
+        client.join_tanda(&friend, &tanda_id);
+        client.join_tanda(&stranger, &tanda_id);
+        assert_eq!(client.get_members(&tanda_id).len(), 1);
+        assert_eq!(client.get_join_requests(&tanda_id).len(), 2);
+        assert_eq!(token.balance(&friend), 0);
+        assert_eq!(client.try_join_tanda(&friend, &tanda_id), Err(Ok(TandaError::RequestPending)));
+
+        assert_eq!(
+            client.try_approve_member(&friend, &tanda_id, &friend),
+            Err(Ok(TandaError::NotCreator))
+        );
+        client.approve_member(&creator, &tanda_id, &friend);
+        client.reject_member(&creator, &tanda_id, &stranger);
+
+        // The friend is in with their bond as collateral; the stranger got theirs back
+        assert_eq!(client.get_members(&tanda_id).len(), 2);
+        assert_eq!(client.get_collateral(&tanda_id).get(friend.clone()), Some(100_0000000));
+        assert_eq!(token.balance(&stranger), 100_0000000);
+        assert_eq!(client.get_join_requests(&tanda_id).len(), 0);
+        assert_eq!(
+            client.try_approve_member(&creator, &tanda_id, &stranger),
+            Err(Ok(TandaError::RequestNotFound))
+        );
+        assert_eq!(client.get_unallocated_balance(), 0);
+    }
+
+    #[test]
+    fn test_join_requests_expire_and_can_be_withdrawn() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &0);
+
+        let creator = Address::generate(&env);
+        let late = Address::generate(&env);
+        let undecided = Address::generate(&env);
+        let next = Address::generate(&env);
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        for m in [&creator, &late, &undecided, &next] {
+            mint(&env, &eurc, m, 100_0000000);
+        }
+
+        let mut config = client.default_config();
+        config.visibility = Visibility::ApprovalRequired;
+        config.collateral_multiple = 1;
+        config.request_expiry_days = 2;
+        let name = String::from_str(&env, "Private");
+        let tanda_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &5, &config);
+
+        client.join_tanda(&late, &tanda_id);
+        advance_days(&env, 3);
+
+        // The request lapsed: it is no longer listed and can't be approved
+        assert_eq!(client.get_join_requests(&tanda_id).len(), 0);
+        assert_eq!(
+            client.try_approve_member(&creator, &tanda_id, &late),
+            Err(Ok(TandaError::RequestExpired))
+        );
+
+        // The next request prunes it and returns the bond
+        client.join_tanda(&undecided, &tanda_id);
+        assert_eq!(token.balance(&late), 100_0000000);
+
+        // A requester can withdraw by leaving; the rest are refunded on start
+        client.leave_tanda(&undecided, &tanda_id);
+        assert_eq!(token.balance(&undecided), 100_0000000);
+
+        client.join_tanda(&next, &tanda_id);
+        assert_eq!(client.try_start_tanda(&creator, &tanda_id), Err(Ok(TandaError::NotEnoughMembers)));
+        client.approve_member(&creator, &tanda_id, &next);
+        client.join_tanda(&undecided, &tanda_id);
+        client.start_tanda(&creator, &tanda_id);
+        assert_eq!(token.balance(&undecided), 100_0000000);
+        assert_eq!(client.get_unallocated_balance(), 0);
+
+        let mut bad = client.default_config();
+        bad.request_expiry_days = 0;
+        assert_eq!(
+            client.try_create_tanda_with_config(&creator, &name, &100_0000000, &5, &bad),
+            Err(Ok(TandaError::InvalidRequestExpiry))
+        );
+    }
+
+    #[test]
+    fn test_invite_codes_gate_joining() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let creator = Address::generate(&env);
+        let alice = Address::generate(&env);
+        let bob = Address::generate(&env);
+        let carol = Address::generate(&env);
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+
+        let code = |s: &str| Bytes::from_slice(&env, s.as_bytes());
+        let hash = |s: &str| -> BytesN<32> { env.crypto().sha256(&code(s)).into() };
+        let now = env.ledger().timestamp();
+
+        let mut config = client.default_config();
+        config.invite_codes.push_back(InviteCode {
+            hash: hash("family"),
+            single_use: true,
+            expires_at: 0,
+        });
+        config.invite_codes.push_back(InviteCode {
+            hash: hash("friends"),
+            single_use: false,
+            expires_at: now + 5 * SECONDS_PER_DAY,
+        });
+        let name = String::from_str(&env, "Invite only");
+        let tanda_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &6, &config);
+
+        // Knowing the tanda ID is not enough
+        assert_eq!(client.try_join_tanda(&alice, &tanda_id), Err(Ok(TandaError::InviteRequired)));
+        assert_eq!(
+            client.try_join_with_code(&alice, &tanda_id, &code("guess")),
+            Err(Ok(TandaError::InvalidInviteCode))
+        );
+
+        // A single-use code works once; a shared code works until revoked
+        client.join_with_code(&alice, &tanda_id, &code("family"));
+        assert_eq!(
+            client.try_join_with_code(&bob, &tanda_id, &code("family")),
+            Err(Ok(TandaError::InvalidInviteCode))
+        );
+        client.join_with_code(&bob, &tanda_id, &code("friends"));
+        assert_eq!(client.get_invite_codes(&tanda_id).len(), 1);
+
+        client.revoke_invite_code(&creator, &tanda_id, &hash("friends"));
+        assert_eq!(
+            client.try_join_with_code(&carol, &tanda_id, &code("friends")),
+            Err(Ok(TandaError::InvalidInviteCode))
+        );
+
+        // Codes stop working once they expire
+        let expiring = InviteCode {
+            hash: hash("weekend"),
+            single_use: false,
+            expires_at: now + SECONDS_PER_DAY,
+        };
+        client.add_invite_code(&creator, &tanda_id, &expiring);
+        advance_days(&env, 2);
+        assert_eq!(
+            client.try_join_with_code(&carol, &tanda_id, &code("weekend")),
+            Err(Ok(TandaError::InviteExpired))
+        );
+
+        assert_eq!(client.get_members(&tanda_id).len(), 3);
+    }
+
+    #[test]
+    fn test_fixed_schedule_due_dates() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+
+        client.initialize(&admin, &eurc, &commission, &0);
+
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let c = Address::generate(&env);
+        mint(&env, &eurc, &a, CREATE_TANDA_FEE);
+        for m in [&a, &b, &c] {
+            mint(&env, &eurc, m, 300_0000000);
+        }
+
+        let mut config = client.default_config();
+        config.schedule = Schedule::Fixed;
+        config.period_days = 7;
+        config.delinquency_days = 3;
+        let name = String::from_str(&env, "Weekly");
+        let tanda_id = client.create_tanda_with_config(&a, &name, &100_0000000, &3, &config);
+        client.join_tanda(&b, &tanda_id);
+        client.join_tanda(&c, &tanda_id);
+        client.start_tanda(&a, &tanda_id);
+
+        let started_at = client.get_tanda(&tanda_id).started_at;
+        assert_eq!(client.get_due_date(&tanda_id), started_at + 7 * SECONDS_PER_DAY);
+
+        // Everyone paid on day 1, but the cycle isn't due yet
+        advance_days(&env, 1);
+        for m in [&a, &b, &c] {
+            client.deposit(m, &tanda_id);
+        }
+        assert!(!client.advance(&tanda_id));
+        assert_eq!(client.try_trigger_payout(&tanda_id), Err(Ok(TandaError::PayoutNotDue)));
+
+        // It can still go out early if every member agrees
+        client.consent_early_payout(&a, &tanda_id);
+        client.consent_early_payout(&b, &tanda_id);
+        assert_eq!(
+            client.try_consent_early_payout(&b, &tanda_id),
+            Err(Ok(TandaError::AlreadyConsented))
+        );
+        assert_eq!(client.try_trigger_payout(&tanda_id), Err(Ok(TandaError::PayoutNotDue)));
+        client.consent_early_payout(&c, &tanda_id);
+        client.trigger_payout(&tanda_id);
+
+        // An early payout doesn't move the calendar
+        assert_eq!(client.get_tanda(&tanda_id).current_cycle, 2);
+        assert_eq!(client.get_due_date(&tanda_id), started_at + 14 * SECONDS_PER_DAY);
+
+        // Delinquency counts from the due date: C is only expellable 3 days after it
+        keep_token_alive(&env, &eurc);
+        advance_days(&env, 14);
+        client.deposit(&a, &tanda_id);
+        client.deposit(&b, &tanda_id);
+        assert!(!client.can_expel(&tanda_id, &c));
+        assert!(!client.advance(&tanda_id));
+
+        advance_days(&env, 2);
+        assert!(client.can_expel(&tanda_id, &c));
+        assert!(client.advance(&tanda_id));
+
+        // C's slot is gone, so B's payout was the last one
+        let tanda = client.get_tanda(&tanda_id);
+        assert_eq!(tanda.status, TandaStatus::Completed);
+    }
+
+    #[test]
+    fn test_schedule_validation() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+
+        client.initialize(&admin, &eurc, &commission, &0);
+
+        let creator = Address::generate(&env);
+        let member = Address::generate(&env);
+        let name = String::from_str(&env, "Schedules");
+
+        let mut config = client.default_config();
+        config.schedule = Schedule::Fixed;
+        assert_eq!(
+            client.try_create_tanda_with_config(&creator, &name, &100_0000000, &3, &config),
+            Err(Ok(TandaError::InvalidPeriod))
+        );
+
+        // Consent only applies to tandas with due dates
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        let tanda_id = client.create_tanda(&creator, &name, &100_0000000, &3);
+        client.join_tanda(&member, &tanda_id);
+        client.start_tanda(&creator, &tanda_id);
+        assert_eq!(client.get_due_date(&tanda_id), 0);
+        assert_eq!(
+            client.try_consent_early_payout(&creator, &tanda_id),
+            Err(Ok(TandaError::NotScheduled))
+        );
+    }
+
+    #[test]
+    fn test_forming_deadline_cancels_and_refunds_fee() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let creator = Address::generate(&env);
+        let member = Address::generate(&env);
+        let name = String::from_str(&env, "Deadline");
+
+        let mut config = client.default_config();
+        config.forming_days = 91;
+        assert_eq!(
+            client.try_create_tanda_with_config(&creator, &name, &100_0000000, &3, &config),
+            Err(Ok(TandaError::InvalidFormingDeadline))
+        );
+
+        // The fee is held by the contract while the tanda forms
+        config.forming_days = 5;
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        let tanda_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &3, &config);
+        client.join_tanda(&member, &tanda_id);
+        assert_eq!(token.balance(&contract_id), CREATE_TANDA_FEE);
+        assert_eq!(client.get_unallocated_balance(), 0);
+
+        assert_eq!(
+            client.try_expire_forming(&tanda_id),
+            Err(Ok(TandaError::FormingNotExpired))
+        );
+
+        advance_days(&env, 5);
+        assert_eq!(client.expire_forming(&tanda_id), TandaStatus::Cancelled);
+        assert_eq!(token.balance(&creator), CREATE_TANDA_FEE);
+        assert_eq!(token.balance(&commission), 0);
+        assert_eq!(token.balance(&contract_id), 0);
+        assert_eq!(client.get_unallocated_balance(), 0);
+
+        assert_eq!(
+            client.try_expire_forming(&tanda_id),
+            Err(Ok(TandaError::NotForming))
+        );
+
+        // Without a deadline the fee goes straight to commission
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        let plain_id = client.create_tanda(&creator, &name, &100_0000000, &3);
+        assert_eq!(token.balance(&commission), CREATE_TANDA_FEE);
+        assert_eq!(
+            client.try_expire_forming(&plain_id),
+            Err(Ok(TandaError::NoFormingDeadline))
+        );
+    }
+
+    #[test]
+    fn test_forming_deadline_starts_if_ready() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let creator = Address::generate(&env);
+        let member = Address::generate(&env);
+        let name = String::from_str(&env, "Ready");
+
+        let mut config = client.default_config();
+        config.forming_days = 3;
+        config.forming_expiry = FormingExpiry::StartIfReady;
+
+        mint(&env, &eurc, &creator, 2 * CREATE_TANDA_FEE);
+        let ready_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &5, &config);
+        client.join_tanda(&member, &ready_id);
+        let lonely_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &5, &config);
+
+        advance_days(&env, 3);
+
+        // Enough members: the tanda starts and the fee is earned
+        assert_eq!(client.expire_forming(&ready_id), TandaStatus::Active);
+        let tanda = client.get_tanda(&ready_id);
+        assert_eq!(tanda.total_cycles, 2);
+        assert_eq!(tanda.current_cycle, 1);
+        assert_eq!(token.balance(&commission), CREATE_TANDA_FEE);
+
+        // Too few members: cancelled and refunded
+        assert_eq!(client.expire_forming(&lonely_id), TandaStatus::Cancelled);
+        assert_eq!(token.balance(&creator), CREATE_TANDA_FEE);
+        assert_eq!(client.get_unallocated_balance(), 0);
+    }
+
+    #[test]
+    fn test_auto_start_when_full() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let creator = Address::generate(&env);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let name = String::from_str(&env, "Self starter");
+
+        let mut config = client.default_config();
+        config.auto_start_when_full = true;
+        config.visibility = Visibility::ApprovalRequired;
+
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        let tanda_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &3, &config);
+
+        client.join_tanda(&a, &tanda_id);
+        client.approve_member(&creator, &tanda_id, &a);
+        assert_eq!(client.get_tanda(&tanda_id).status, TandaStatus::Forming);
+
+        // Approving the member who fills the last slot starts the tanda
+        client.join_tanda(&b, &tanda_id);
+        client.approve_member(&creator, &tanda_id, &b);
+
+        let tanda = client.get_tanda(&tanda_id);
+        assert_eq!(tanda.status, TandaStatus::Active);
+        assert_eq!(tanda.current_cycle, 1);
+        assert_eq!(tanda.total_cycles, 3);
+        assert_eq!(tanda.started_at, env.ledger().timestamp());
+
+        // Open tandas start on the filling join
+        config.visibility = Visibility::Open;
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        let open_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &2, &config);
+        client.join_tanda(&a, &open_id);
+        assert_eq!(client.get_tanda(&open_id).status, TandaStatus::Active);
+    }
+
+    #[test]
+    fn test_payout_order_strategies() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let creator = Address::generate(&env);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let name = String::from_str(&env, "Order");
+        let mut config = client.default_config();
+
+        // Reverse: last to join is paid first
+        config.payout_order = PayoutOrder::Reverse;
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        let reverse_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &3, &config);
+        client.join_tanda(&a, &reverse_id);
+        client.join_tanda(&b, &reverse_id);
+        assert_eq!(
+            client.try_assign_payout_order(&creator, &reverse_id, &Vec::from_array(&env, [a.clone()])),
+            Err(Ok(TandaError::OrderNotAssignable))
+        );
+        client.start_tanda(&creator, &reverse_id);
+        assert_eq!(client.get_beneficiary(&reverse_id), b);
+        for member in client.get_members(&reverse_id).iter() {
+            let expected = if member.address == b { 0 } else if member.address == a { 1 } else { 2 };
+            assert_eq!(member.position, expected);
+        }
+
+        // Creator assigned: must be a permutation of the members
+        config.payout_order = PayoutOrder::CreatorAssigned;
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        let assigned_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &3, &config);
+        client.join_tanda(&a, &assigned_id);
+        client.join_tanda(&b, &assigned_id);
+
+        let short = Vec::from_array(&env, [a.clone(), b.clone()]);
+        let repeated = Vec::from_array(&env, [a.clone(), b.clone(), a.clone()]);
+        let stranger = Vec::from_array(&env, [a.clone(), b.clone(), Address::generate(&env)]);
+        for bad in [short, repeated, stranger] {
+            assert_eq!(
+                client.try_assign_payout_order(&creator, &assigned_id, &bad),
+                Err(Ok(TandaError::InvalidPayoutOrder))
+            );
+        }
+
+        let order = Vec::from_array(&env, [a.clone(), creator.clone(), b.clone()]);
+        client.assign_payout_order(&creator, &assigned_id, &order);
+        client.start_tanda(&creator, &assigned_id);
+        assert_eq!(client.get_beneficiary(&assigned_id), a);
+        assert_eq!(client.get_tanda(&assigned_id).total_cycles, 3);
+
+        // Random: still one slot per member
+        config.payout_order = PayoutOrder::Random;
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        let random_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &3, &config);
+        client.join_tanda(&a, &random_id);
+        client.join_tanda(&b, &random_id);
+        client.start_tanda(&creator, &random_id);
+
+        let mut taken = [false; 3];
+        for member in client.get_members(&random_id).iter() {
+            assert!(!taken[member.position as usize]);
+            taken[member.position as usize] = true;
+        }
+        assert_eq!(taken, [true; 3]);
+    }
+
+    #[test]
+    fn test_lottery_payout_order() {
+        // Run the same lottery twice with one PRNG seed: the draws replay
+        let mut draws = [[0u32; 4]; 2];
+        for draw in draws.iter_mut() {
+            let (env, admin, eurc, commission) = setup_env();
+            env.host().set_base_prng_seed([7; 32]).unwrap();
+
+            let contract_id = env.register_contract(None, TandaContract);
+            let client = TandaContractClient::new(&env, &contract_id);
+            let token = TokenClient::new(&env, &eurc);
+
+            client.initialize(&admin, &eurc, &commission, &0);
+
+            let members = Vec::from_array(&env, [
+                Address::generate(&env),
+                Address::generate(&env),
+                Address::generate(&env),
+                Address::generate(&env),
+            ]);
+            let creator = members.get(0).unwrap();
+            mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+            for m in members.iter() {
+                mint(&env, &eurc, &m, 400_0000000);
+            }
+
+            let mut config = client.default_config();
+            config.payout_order = PayoutOrder::Lottery;
+            let name = String::from_str(&env, "Lottery");
+            let tanda_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &4, &config);
+            for m in members.iter().skip(1) {
+                client.join_tanda(&m, &tanda_id);
+            }
+            client.start_tanda(&creator, &tanda_id);
+
+            // Nobody is the beneficiary until the cycle's draw
+            assert_eq!(
+                client.try_get_beneficiary(&tanda_id),
+                Err(Ok(TandaError::BeneficiaryNotFound))
+            );
+
+            for cycle in 0..4 {
+                for m in members.iter() {
+                    client.deposit(&m, &tanda_id);
+                }
+                client.trigger_payout(&tanda_id);
+
+                // The draw leaves the winner in the cycle's slot
+                let winner = client.get_members(&tanda_id).iter()
+                    .find(|m| m.position == cycle)
+                    .unwrap();
+                assert_eq!(winner.status, MemberStatus::Received);
+                draw[cycle as usize] = members.first_index_of(winner.address).unwrap();
+            }
+
+            // Each member paid four cycles in and drew one full pot
+            assert_eq!(client.get_tanda(&tanda_id).status, TandaStatus::Completed);
+            for m in members.iter() {
+                assert_eq!(token.balance(&m), 400_0000000);
+            }
+            let mut drawn = *draw;
+            drawn.sort();
+            assert_eq!(drawn, [0, 1, 2, 3]);
+        }
+        assert_eq!(draws[0], draws[1]);
+    }
+
+    #[test]
+    fn test_discount_auction() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &0);
+
+        let creator = Address::generate(&env);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        for m in [&creator, &a, &b] {
+            mint(&env, &eurc, m, 200_0000000);
+        }
+
+        let mut config = client.default_config();
+        config.payout_order = PayoutOrder::Auction;
+        let name = String::from_str(&env, "Auction");
+        let tanda_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &3, &config);
+        client.join_tanda(&a, &tanda_id);
+        client.join_tanda(&b, &tanda_id);
+        client.start_tanda(&creator, &tanda_id);
+
+        // Bids must be a discount within the pot
+        assert_eq!(client.try_place_bid(&a, &tanda_id, &0), Err(Ok(TandaError::InvalidBid)));
+        assert_eq!(
+            client.try_place_bid(&a, &tanda_id, &300_0000000),
+            Err(Ok(TandaError::InvalidBid))
+        );
+
+        // Highest discount wins the pot minus the discount
+        client.place_bid(&a, &tanda_id, &30_0000000);
+        client.place_bid(&b, &tanda_id, &50_0000000);
+        assert_eq!(client.get_bids(&tanda_id, &1).len(), 2);
+        for m in [&creator, &a, &b] {
+            client.deposit(m, &tanda_id);
+        }
+        client.trigger_payout(&tanda_id);
+
+        // The discount is paid out as a dividend to the other members
+        let result = client.get_auction_result(&tanda_id, &1).unwrap();
+        assert_eq!(result.winner, b);
+        assert_eq!(result.discount, 50_0000000);
+        assert_eq!(result.dividend, 25_0000000);
+        assert_eq!(result.recipients, 2);
+        assert_eq!(token.balance(&b), 100_0000000 + 250_0000000);
+        assert_eq!(token.balance(&a), 100_0000000 + 25_0000000);
+
+        // The winner can't bid again. With no bids the next unpaid slot gets
+        // the whole pot: B swapped into the creator's slot, so that is A
+        assert_eq!(
+            client.try_place_bid(&b, &tanda_id, &10_0000000),
+            Err(Ok(TandaError::NotEligibleToBid))
+        );
+        for m in [&creator, &a, &b] {
+            client.deposit(m, &tanda_id);
+        }
+        client.trigger_payout(&tanda_id);
+        let result = client.get_auction_result(&tanda_id, &2).unwrap();
+        assert_eq!(result.winner, a);
+        assert_eq!(result.discount, 0);
+        assert_eq!(token.balance(&a), 25_0000000 + 300_0000000);
+        assert_eq!(client.get_unallocated_balance(), 0);
+    }
+
+    // Commitment to a sealed bid: sha256 of the discount followed by the salt
+    fn seal(env: &Env, discount: i128, salt: &BytesN<32>) -> BytesN<32> {
+        let mut preimage = Bytes::from_array(env, &discount.to_be_bytes());
+        preimage.append(&salt.clone().into());
+        env.crypto().sha256(&preimage).into()
+    }
+
+    #[test]
+    fn test_sealed_bids() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &0);
+
+        let creator = Address::generate(&env);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        for m in [&creator, &a, &b] {
+            mint(&env, &eurc, m, 200_0000000);
+        }
+
+        let mut config = client.default_config();
+        config.payout_order = PayoutOrder::Auction;
+        config.sealed_bids = true;
+        let name = String::from_str(&env, "Auction");
+        let tanda_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &3, &config);
+        client.join_tanda(&a, &tanda_id);
+        client.join_tanda(&b, &tanda_id);
+        client.start_tanda(&creator, &tanda_id);
+        let salt_a = BytesN::from_array(&env, &[1; 32]);
+        let salt_b = BytesN::from_array(&env, &[2; 32]);
+
+        // No open bids, and nothing is revealed while bidding is open
+        assert_eq!(
+            client.try_place_bid(&a, &tanda_id, &30_0000000),
+            Err(Ok(TandaError::WrongBidMode))
+        );
+        client.commit_bid(&a, &tanda_id, &seal(&env, 30_0000000, &salt_a));
+        client.commit_bid(&b, &tanda_id, &seal(&env, 50_0000000, &salt_b));
+        assert_eq!(client.get_sealed_bids(&tanda_id, &1).len(), 2);
+        assert_eq!(client.get_bids(&tanda_id, &1).len(), 0);
+        assert_eq!(
+            client.try_reveal_bid(&a, &tanda_id, &30_0000000, &salt_a),
+            Err(Ok(TandaError::RevealNotOpen))
+        );
+
+        // Bidding closes once everyone has deposited
+        assert_eq!(client.try_close_bids(&tanda_id), Err(Ok(TandaError::NotAllDeposited)));
+        for m in [&creator, &a, &b] {
+            client.deposit(m, &tanda_id);
+        }
+        client.close_bids(&tanda_id);
+        assert_eq!(
+            client.try_commit_bid(&creator, &tanda_id, &seal(&env, 10_0000000, &salt_a)),
+            Err(Ok(TandaError::BiddingClosed))
+        );
+
+        // The payout waits until every bid is revealed
+        assert_eq!(
+            client.try_reveal_bid(&a, &tanda_id, &40_0000000, &salt_a),
+            Err(Ok(TandaError::BidMismatch))
+        );
+        client.reveal_bid(&a, &tanda_id, &30_0000000, &salt_a);
+        assert_eq!(client.try_trigger_payout(&tanda_id), Err(Ok(TandaError::RevealPending)));
+        client.reveal_bid(&b, &tanda_id, &50_0000000, &salt_b);
+        assert_eq!(client.get_sealed_bids(&tanda_id, &1).len(), 0);
+        client.trigger_payout(&tanda_id);
+
+        let result = client.get_auction_result(&tanda_id, &1).unwrap();
+        assert_eq!(result.winner, b);
+        assert_eq!(result.discount, 50_0000000);
+        assert_eq!(token.balance(&b), 100_0000000 + 250_0000000);
+
+        // A never reveals in the second cycle: after the window it pays out as if nobody bid
+        client.commit_bid(&a, &tanda_id, &seal(&env, 50_0000000, &salt_a));
+        for m in [&creator, &a, &b] {
+            client.deposit(m, &tanda_id);
+        }
+        client.close_bids(&tanda_id);
+        assert!(!client.advance(&tanda_id));
+        advance_days(&env, 1);
+        assert_eq!(
+            client.try_reveal_bid(&a, &tanda_id, &50_0000000, &salt_a),
+            Err(Ok(TandaError::RevealNotOpen))
+        );
+        client.trigger_payout(&tanda_id);
+        let result = client.get_auction_result(&tanda_id, &2).unwrap();
+        assert_eq!(result.winner, a);
+        assert_eq!(result.discount, 0);
+        assert_eq!(token.balance(&a), 25_0000000 + 300_0000000);
+        assert_eq!(client.get_unallocated_balance(), 0);
+    }
+
+    #[test]
+    fn test_position_swaps() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &0);
+
+        let creator = Address::generate(&env);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        for m in [&creator, &a, &b] {
+            mint(&env, &eurc, m, 100_0000000);
+        }
+
+        let name = String::from_str(&env, "Swaps");
+        let tanda_id = client.create_tanda(&creator, &name, &100_0000000, &3);
+        client.join_tanda(&a, &tanda_id);
+        client.join_tanda(&b, &tanda_id);
+
+        // A swap needs a proposal from the other side
+        assert_eq!(
+            client.try_accept_swap(&b, &creator, &tanda_id),
+            Err(Ok(TandaError::SwapNotFound))
+        );
+
+        // While forming: B goes first, the creator last
+        client.propose_swap(&creator, &b, &tanda_id);
+        assert_eq!(client.get_swap_proposals(&tanda_id).get(creator.clone()), Some(b.clone()));
+        client.accept_swap(&b, &creator, &tanda_id);
+        assert_eq!(client.get_swap_proposals(&tanda_id).len(), 0);
+        client.start_tanda(&creator, &tanda_id);
+        assert_eq!(client.get_beneficiary(&tanda_id), b);
+
+        // Once active: the creator moves up to the open cycle, B takes the last slot
+        client.propose_swap(&creator, &b, &tanda_id);
+        client.accept_swap(&b, &creator, &tanda_id);
+        assert_eq!(client.get_beneficiary(&tanda_id), creator);
+        for member in client.get_members(&tanda_id).iter() {
+            if member.address == b {
+                assert_eq!(member.position, 2);
+            }
+        }
+
+        let before = token.balance(&creator);
+        for m in [&creator, &a, &b] {
+            client.deposit(m, &tanda_id);
+        }
+        client.trigger_payout(&tanda_id);
+        assert_eq!(token.balance(&creator) - before, 200_0000000);
+
+        // A member who has been paid can't trade their slot, and nobody swaps with themselves
+        assert_eq!(
+            client.try_propose_swap(&creator, &b, &tanda_id),
+            Err(Ok(TandaError::SwapNotAllowed))
+        );
+        assert_eq!(
+            client.try_propose_swap(&a, &a, &tanda_id),
+            Err(Ok(TandaError::SwapNotAllowed))
+        );
+    }
+
+    #[test]
+    fn test_position_market() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &0);
+
+        let creator = Address::generate(&env);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        for m in [&creator, &a, &b] {
+            mint(&env, &eurc, m, 100_0000000);
+        }
+        mint(&env, &eurc, &b, 5_0000000);
+
+        let name = String::from_str(&env, "Market");
+        let tanda_id = client.create_tanda(&creator, &name, &100_0000000, &3);
+        client.join_tanda(&a, &tanda_id);
+        client.join_tanda(&b, &tanda_id);
+        client.start_tanda(&creator, &tanda_id);
+
+        // The open cycle's slot is no longer for sale, and premiums must be positive
+        assert_eq!(
+            client.try_list_position(&creator, &tanda_id, &5_0000000),
+            Err(Ok(TandaError::ListingExpired))
+        );
+        assert_eq!(
+            client.try_list_position(&a, &tanda_id, &0),
+            Err(Ok(TandaError::InvalidPremium))
+        );
+
+        // A listing can be withdrawn
+        client.list_position(&a, &tanda_id, &1_0000000);
+        client.cancel_listing(&a, &tanda_id);
+        assert_eq!(client.get_listings(&tanda_id).len(), 0);
+        assert_eq!(
+            client.try_cancel_listing(&a, &tanda_id),
+            Err(Ok(TandaError::ListingNotFound))
+        );
+
+        // Buying pays the premium and swaps the slots at once
+        client.list_position(&a, &tanda_id, &5_0000000);
+        assert_eq!(client.get_listings(&tanda_id).get(a.clone()), Some(5_0000000));
+        client.buy_position(&b, &tanda_id, &a);
+        assert_eq!(token.balance(&a), 105_0000000);
+        assert_eq!(token.balance(&b), 100_0000000);
+        assert_eq!(client.get_listings(&tanda_id).len(), 0);
+        for member in client.get_members(&tanda_id).iter() {
+            if member.address == b {
+                assert_eq!(member.position, 1);
+            } else if member.address == a {
+                assert_eq!(member.position, 2);
+            }
+        }
+
+        // B's listing lapses when B's cycle opens
+        client.list_position(&b, &tanda_id, &5_0000000);
+        for m in [&creator, &a, &b] {
+            client.deposit(m, &tanda_id);
+        }
+        client.trigger_payout(&tanda_id);
+        assert_eq!(client.get_listings(&tanda_id).len(), 0);
+        assert_eq!(
+            client.try_buy_position(&a, &tanda_id, &b),
+            Err(Ok(TandaError::ListingExpired))
+        );
+    }
+
+    #[test]
+    fn test_partial_deposits() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let creator = Address::generate(&env);
+        let a = Address::generate(&env);
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE + 101_0000000);
+        mint(&env, &eurc, &a, 101_0000000);
+
+        let name = String::from_str(&env, "Installments");
+        let tanda_id = client.create_tanda(&creator, &name, &100_0000000, &2);
+        client.join_tanda(&a, &tanda_id);
+        client.start_tanda(&creator, &tanda_id);
+        let fee_paid = token.balance(&commission);
+
+        client.deposit_partial(&a, &tanda_id, &40_0000000);
+        let member = client.get_members(&tanda_id).get(1).unwrap();
+        assert_eq!(member.paid_this_cycle, 40_0000000);
+        assert!(!member.has_deposited);
+
+        // Nobody pays more than what is left of the cycle
+        assert_eq!(
+            client.try_deposit_partial(&a, &tanda_id, &60_0000001),
+            Err(Ok(TandaError::InvalidAmount))
+        );
+
+        // Commission is charged on each piece
+        client.deposit_partial(&a, &tanda_id, &60_0000000);
+        assert_eq!(token.balance(&commission) - fee_paid, 5000000);
+        assert!(client.get_members(&tanda_id).get(1).unwrap().has_deposited);
+        assert_eq!(
+            client.try_deposit_partial(&a, &tanda_id, &1),
+            Err(Ok(TandaError::AlreadyDeposited))
+        );
+
+        // A full deposit pays what is left
+        client.deposit_partial(&creator, &tanda_id, &25_0000000);
+        assert!(!client.all_deposited(&tanda_id));
+        client.deposit(&creator, &tanda_id);
+        assert_eq!(client.get_escrow(&tanda_id), 200_0000000);
+        assert_eq!(token.balance(&creator), 5000000);
+
+        // Installments start over with the next cycle
+        client.trigger_payout(&tanda_id);
+        let member = client.get_members(&tanda_id).get(0).unwrap();
+        assert_eq!(member.paid_this_cycle, 0);
+        assert_eq!(client.get_unallocated_balance(), 0);
+    }
+
+    #[test]
+    fn test_prepaid_cycles() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &50);
 
-        (env, admin, eurc, commission)
+        let creator = Address::generate(&env);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE + 201_0000000);
+        mint(&env, &eurc, &a, 301_5000000);
+        mint(&env, &eurc, &b, 111_0000000);
+
+        let name = String::from_str(&env, "Prepaid");
+        let tanda_id = client.create_tanda(&creator, &name, &100_0000000, &3);
+        client.join_tanda(&a, &tanda_id);
+        client.join_tanda(&b, &tanda_id);
+        client.start_tanda(&creator, &tanda_id);
+
+        // Prepaying covers the open cycle right away
+        client.prepay(&a, &tanda_id, &3);
+        assert_eq!(token.balance(&a), 0);
+        assert!(client.get_members(&tanda_id).get(1).unwrap().has_deposited);
+        assert_eq!(
+            client.try_prepay(&creator, &tanda_id, &4),
+            Err(Ok(TandaError::InvalidPrepayment))
+        );
+
+        // After an installment only the later cycles are left to prepay
+        client.deposit(&creator, &tanda_id);
+        client.deposit_partial(&b, &tanda_id, &10_0000000);
+        assert_eq!(
+            client.try_prepay(&b, &tanda_id, &3),
+            Err(Ok(TandaError::InvalidPrepayment))
+        );
+        client.prepay(&b, &tanda_id, &1);
+        let prepaid = client.get_prepaid(&tanda_id);
+        assert_eq!(prepaid.get(a.clone()), Some(2));
+        assert_eq!(prepaid.get(b.clone()), Some(1));
+
+        // b never completes the cycle: expelled, installment and prepayment refunded
+        let b_before = token.balance(&b);
+        advance_days(&env, 7);
+        client.advance(&tanda_id);
+        assert_eq!(token.balance(&b), b_before + 10_0000000 + 100_5000000);
+        assert_eq!(client.get_prepaid(&tanda_id).get(a.clone()), Some(1));
+        assert!(!client.all_deposited(&tanda_id));
+
+        // The new cycle is already paid for by a's prepayment
+        let a_member = client.get_members(&tanda_id).get(1).unwrap();
+        assert!(a_member.has_deposited);
+        client.deposit(&creator, &tanda_id);
+        client.trigger_payout(&tanda_id);
+
+        // Expelling b shortened the rotation: a's last prepaid cycle is refunded
+        assert_eq!(client.get_tanda(&tanda_id).status, TandaStatus::Completed);
+        assert_eq!(token.balance(&a), 200_0000000 + 100_5000000);
+        assert_eq!(client.get_prepaid(&tanda_id).len(), 0);
+        assert_eq!(client.get_unallocated_balance(), 0);
+        assert_eq!(token.balance(&contract_id), 0);
     }
 
     #[test]
-    fn test_create_and_start_tanda() {
+    fn test_grace_window_and_late_fees() {
         let (env, admin, eurc, commission) = setup_env();
 
         let contract_id = env.register_contract(None, TandaContract);
         let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
 
-        client.initialize(&admin, &eurc, &commission, &50);
+        client.initialize(&admin, &eurc, &commission, &0);
 
         let creator = Address::generate(&env);
-        let member2 = Address::generate(&env);
-        let name = String::from_str(&env, "Test Tanda");
+        let a = Address::generate(&env);
+        let name = String::from_str(&env, "Grace");
+        mint(&env, &eurc, &creator, 3 * CREATE_TANDA_FEE + 300_0000000);
+        mint(&env, &eurc, &a, 600_0000000);
+
+        let mut config = client.default_config();
+        config.delinquency_days = 3;
+        config.grace_days = 15;
+        assert_eq!(
+            client.try_create_tanda_with_config(&creator, &name, &100_0000000, &2, &config),
+            Err(Ok(TandaError::InvalidGracePeriod))
+        );
+        config.grace_days = 2;
+        config.late_fee_bps = 1001;
+        assert_eq!(
+            client.try_create_tanda_with_config(&creator, &name, &100_0000000, &2, &config),
+            Err(Ok(TandaError::LateFeeTooHigh))
+        );
+        config.late_fee_bps = 500;
 
-        // Create tanda
-        let tanda_id = client.create_tanda(&creator, &name, &100_0000000, &5);
+        let tanda_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &2, &config);
+        client.join_tanda(&a, &tanda_id);
+        client.start_tanda(&creator, &tanda_id);
+        client.deposit(&creator, &tanda_id);
 
-        // Join
-        client.join_tanda(&member2, &tanda_id);
+        // One day late: in the grace window, nobody can be expelled yet
+        advance_days(&env, 4);
+        let status = client.get_advance_status(&tanda_id);
+        assert!(!status.can_advance);
+        assert_eq!(status.will_expel_count, 0);
+        assert!(status.in_grace);
+        assert_eq!(status.grace_ends_at, client.get_tanda(&tanda_id).last_payout_at + 5 * 86400);
+        assert!(!client.can_expel(&tanda_id, &a));
+        assert_eq!(
+            client.try_expel_delinquent(&tanda_id, &a),
+            Err(Ok(TandaError::DelinquencyNotPassed))
+        );
 
-        // Start
+        // Paying late costs 5%, which goes to the beneficiary
+        client.deposit(&a, &tanda_id);
+        assert_eq!(token.balance(&a), 495_0000000);
+        let creator_before = token.balance(&creator);
+        client.advance(&tanda_id);
+        assert_eq!(token.balance(&creator), creator_before + 205_0000000);
+
+        // Past the grace window the late member is expelled
+        client.deposit(&a, &tanda_id);
+        keep_token_alive(&env, &eurc);
+        advance_days(&env, 6);
+        let status = client.get_advance_status(&tanda_id);
+        assert!(!status.in_grace);
+        assert_eq!(status.will_expel_count, 1);
+        assert!(client.can_expel(&tanda_id, &creator));
+
+        // Late fees can feed the safety fund instead
+        config.late_fee_to = LateFeeRecipient::SafetyFund;
+        let fund_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &2, &config);
+        client.join_tanda(&a, &fund_id);
+        client.start_tanda(&creator, &fund_id);
+        client.deposit(&creator, &fund_id);
+        advance_days(&env, 4);
+        client.deposit(&a, &fund_id);
+        assert_eq!(client.get_safety_fund(&fund_id), 5_0000000);
+        let entry = client.get_safety_fund_history(&fund_id).get(0).unwrap();
+        assert_eq!(entry.movement, FundMovement::LateFee);
+        assert_eq!(entry.account, Some(a.clone()));
+
+        // A late fee is not refunded when the member is expelled from another slot
+        config.late_fee_to = LateFeeRecipient::Beneficiary;
+        config.max_slots_per_member = 2;
+        let slots_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &3, &config);
+        client.join_tanda(&a, &slots_id);
+        client.join_tanda(&a, &slots_id);
+        client.start_tanda(&creator, &slots_id);
+        client.deposit(&creator, &slots_id);
+        advance_days(&env, 4);
+        let a_before = token.balance(&a);
+        client.deposit_slot(&a, &slots_id, &1);
+        advance_days(&env, 2);
+        let creator_before = token.balance(&creator);
+        client.advance(&slots_id);
+        assert_eq!(client.get_members(&slots_id).get(1).unwrap().status, MemberStatus::Expelled);
+        assert_eq!(a_before - token.balance(&a), 105_0000000);
+        assert_eq!(token.balance(&creator) - creator_before, 205_0000000);
+        assert_eq!(client.get_unallocated_balance(), 0);
+    }
+
+    #[test]
+    fn test_waitlist_replaces_expelled_member() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &0);
+
+        let creator = Address::generate(&env);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let w = Address::generate(&env);
+        let x = Address::generate(&env);
+        mint(&env, &eurc, &creator, 2 * CREATE_TANDA_FEE + 300_0000000);
+        mint(&env, &eurc, &a, 300_0000000);
+        mint(&env, &eurc, &b, 100_0000000);
+        mint(&env, &eurc, &w, 300_0000000);
+        mint(&env, &eurc, &x, 300_0000000);
+
+        // Private tandas have no waitlist
+        let mut config = client.default_config();
+        config.visibility = Visibility::ApprovalRequired;
+        let name = String::from_str(&env, "Private");
+        let private_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &2, &config);
+        client.join_tanda(&a, &private_id);
+        client.approve_member(&creator, &private_id, &a);
+        client.start_tanda(&creator, &private_id);
+        assert_eq!(
+            client.try_join_waitlist(&w, &private_id),
+            Err(Ok(TandaError::WaitlistUnavailable))
+        );
+
+        let name = String::from_str(&env, "Waitlist");
+        let tanda_id = client.create_tanda(&creator, &name, &100_0000000, &3);
+        client.join_tanda(&a, &tanda_id);
+        client.join_tanda(&b, &tanda_id);
         client.start_tanda(&creator, &tanda_id);
+        for m in [&creator, &a, &b] {
+            client.deposit(m, &tanda_id);
+        }
+        client.trigger_payout(&tanda_id);
+
+        // Joining escrows the most a replacement can owe
+        assert_eq!(client.try_join_waitlist(&a, &tanda_id), Err(Ok(TandaError::AlreadyMember)));
+        client.join_waitlist(&w, &tanda_id);
+        assert_eq!(token.balance(&w), 0);
+        assert_eq!(client.try_join_waitlist(&w, &tanda_id), Err(Ok(TandaError::RequestPending)));
+        client.join_waitlist(&x, &tanda_id);
+        client.leave_waitlist(&x, &tanda_id);
+        assert_eq!(token.balance(&x), 300_0000000);
+        client.join_waitlist(&x, &tanda_id);
+
+        // b misses cycle 2 and is replaced by w, who catches up on cycle 1
+        client.deposit(&creator, &tanda_id);
+        client.deposit(&a, &tanda_id);
+        advance_days(&env, 7);
+        client.advance(&tanda_id);
 
         let tanda = client.get_tanda(&tanda_id);
-        assert_eq!(tanda.status, TandaStatus::Active);
-        assert_eq!(tanda.current_cycle, 1);
-        assert_eq!(tanda.total_cycles, 2);
+        assert_eq!(tanda.total_cycles, 3);
+        assert_eq!(tanda.active_members, 3);
+        assert_eq!(tanda.current_cycle, 3);
+        assert_eq!(token.balance(&b), 100_0000000);
+        assert_eq!(token.balance(&w), 100_0000000);
+        assert_eq!(client.get_waitlist(&tanda_id).len(), 1);
+
+        // w takes b's place in the rotation and receives the full pot
+        assert_eq!(client.get_beneficiary(&tanda_id), w);
+        for m in [&creator, &a, &w] {
+            client.deposit(m, &tanda_id);
+        }
+        client.trigger_payout(&tanda_id);
+        assert_eq!(token.balance(&w), 300_0000000);
+
+        // Anyone still waiting is refunded at completion
+        assert_eq!(client.get_tanda(&tanda_id).status, TandaStatus::Completed);
+        assert_eq!(token.balance(&x), 300_0000000);
+        assert_eq!(client.get_unallocated_balance(), 0);
+        assert_eq!(token.balance(&contract_id), 0);
     }
 
     #[test]
-    fn test_deposit_and_payout() {
+    fn test_waitlist_replacement_pays_deposit_fees() {
         let (env, admin, eurc, commission) = setup_env();
 
         let contract_id = env.register_contract(None, TandaContract);
         let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
 
         client.initialize(&admin, &eurc, &commission, &50);
 
         let creator = Address::generate(&env);
-        let member2 = Address::generate(&env);
-        let name = String::from_str(&env, "Test Tanda");
-
-        // Setup tanda
-        let tanda_id = client.create_tanda(&creator, &name, &100_0000000, &5);
-        client.join_tanda(&member2, &tanda_id);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let w = Address::generate(&env);
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE + 205_0000000);
+        mint(&env, &eurc, &a, 205_0000000);
+        mint(&env, &eurc, &b, 102_5000000);
+        mint(&env, &eurc, &w, 302_5000000);
+
+        let mut config = client.default_config();
+        config.safety_fund_bps = 200;
+        let name = String::from_str(&env, "Waitlist fees");
+        let tanda_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &3, &config);
+        client.join_tanda(&a, &tanda_id);
+        client.join_tanda(&b, &tanda_id);
         client.start_tanda(&creator, &tanda_id);
+        for m in [&creator, &a, &b] {
+            client.deposit(m, &tanda_id);
+        }
+        client.trigger_payout(&tanda_id);
 
-        // Mint tokens and deposit
-        let token = TokenClient::new(&env, &eurc);
-        token.mint(&creator, &200_0000000);
-        token.mint(&member2, &200_0000000);
+        // Two cycles, plus the open cycle's fund share and commission
+        client.join_waitlist(&w, &tanda_id);
+        assert_eq!(token.balance(&w), 0);
 
         client.deposit(&creator, &tanda_id);
-        client.deposit(&member2, &tanda_id);
+        client.deposit(&a, &tanda_id);
+        let fee_paid = token.balance(&commission);
+        let fund = client.get_safety_fund(&tanda_id);
+        advance_days(&env, 7);
+        client.advance(&tanda_id);
+
+        // w pays into cycle 2 what a regular deposit would
+        assert_eq!(token.balance(&w), 100_0000000);
+        assert_eq!(token.balance(&commission) - fee_paid, 5000000);
+        assert_eq!(client.get_safety_fund(&tanda_id) - fund, 2_0000000);
+        assert_eq!(client.get_contributions(&tanda_id, &2).get(w.clone()), Some(100_0000000));
+        assert_eq!(client.get_tanda(&tanda_id).current_cycle, 3);
+        assert_eq!(client.get_unallocated_balance(), 0);
+    }
 
-        // Check all deposited
-        assert!(client.all_deposited(&tanda_id));
+    #[test]
+    fn test_multiple_slots_per_member() {
+        let (env, admin, eurc, commission) = setup_env();
 
-        // Trigger payout
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let c = Address::generate(&env);
+        mint(&env, &eurc, &a, CREATE_TANDA_FEE + 1000_0000000);
+        mint(&env, &eurc, &b, 500_0000000);
+        mint(&env, &eurc, &c, 500_0000000);
+
+        let name = String::from_str(&env, "Slots");
+        let mut config = client.default_config();
+        config.max_slots_per_member = 5;
+        assert_eq!(
+            client.try_create_tanda_with_config(&a, &name, &100_0000000, &4, &config),
+            Err(Ok(TandaError::InvalidSlotLimit))
+        );
+        config.max_slots_per_member = 2;
+        config.collateral_multiple = 1;
+
+        // A takes a second slot, and a second bond with it
+        let tanda_id = client.create_tanda_with_config(&a, &name, &100_0000000, &4, &config);
+        client.join_tanda(&a, &tanda_id);
+        assert_eq!(client.try_join_tanda(&a, &tanda_id), Err(Ok(TandaError::AlreadyMember)));
+        assert_eq!(client.get_collateral(&tanda_id).get(a.clone()), Some(200_0000000));
+        client.join_tanda(&b, &tanda_id);
+        client.join_tanda(&c, &tanda_id);
+        client.start_tanda(&a, &tanda_id);
+
+        let members = client.get_members(&tanda_id);
+        assert_eq!(members.len(), 4);
+        assert_eq!(client.get_tanda(&tanda_id).total_cycles, 4);
+        let second = members.get(1).unwrap();
+        assert_eq!((second.address, second.slot, second.position), (a.clone(), 1, 1));
+
+        // One deposit covers both of A's slots
+        let before = token.balance(&a);
+        client.deposit(&a, &tanda_id);
+        assert_eq!(before - token.balance(&a), 201_0000000);
+        assert_eq!(client.get_escrow(&tanda_id), 200_0000000);
+        assert_eq!(client.try_deposit(&a, &tanda_id), Err(Ok(TandaError::AlreadyDeposited)));
+        client.deposit(&b, &tanda_id);
+        client.deposit_slot(&c, &tanda_id, &0);
+        assert_eq!(client.try_deposit_slot(&c, &tanda_id, &1), Err(Ok(TandaError::NotMember)));
+
+        let before = token.balance(&a);
         client.trigger_payout(&tanda_id);
+        assert_eq!(token.balance(&a) - before, 400_0000000);
+        assert_eq!(client.get_beneficiary(&tanda_id), a);
+
+        // Slots are paid one at a time too; only the unpaid one is expelled
+        client.deposit_slot(&a, &tanda_id, &1);
+        client.deposit(&b, &tanda_id);
+        client.deposit(&c, &tanda_id);
+        assert!(!client.all_deposited(&tanda_id));
+        keep_token_alive(&env, &eurc);
+        advance_days(&env, 7);
+        assert!(client.can_expel(&tanda_id, &a));
+        client.advance(&tanda_id);
 
-        let tanda = client.get_tanda(&tanda_id);
-        assert_eq!(tanda.current_cycle, 2);
+        let members = client.get_members(&tanda_id);
+        assert_eq!(members.get(0).unwrap().status, MemberStatus::Expelled);
+        assert_eq!(members.get(1).unwrap().status, MemberStatus::Received);
+        assert_eq!(client.get_tanda(&tanda_id).active_members, 3);
+        assert_eq!(client.get_collateral(&tanda_id).get(a.clone()), Some(100_0000000));
+        assert_eq!(client.get_unallocated_balance(), 0);
     }
 
     #[test]
-    fn test_expel_delinquent() {
+    fn test_shared_slots() {
         let (env, admin, eurc, commission) = setup_env();
 
         let contract_id = env.register_contract(None, TandaContract);
         let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
 
         client.initialize(&admin, &eurc, &commission, &50);
 
-        let creator = Address::generate(&env);
-        let delinquent = Address::generate(&env);
-        let name = String::from_str(&env, "Test Tanda");
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let d = Address::generate(&env);
+        let e = Address::generate(&env);
+        mint(&env, &eurc, &a, 2 * CREATE_TANDA_FEE + 500_0000000);
+        mint(&env, &eurc, &b, 500_0000000);
+        mint(&env, &eurc, &d, 500_0000000);
+        mint(&env, &eurc, &e, 500_0000000);
+
+        // Only open tandas take shared slots
+        let mut config = client.default_config();
+        config.visibility = Visibility::ApprovalRequired;
+        let name = String::from_str(&env, "Private");
+        let private_id = client.create_tanda_with_config(&a, &name, &100_0000000, &3, &config);
+        let mut shares = Map::new(&env);
+        shares.set(d.clone(), 6000);
+        shares.set(e.clone(), 4000);
+        assert_eq!(
+            client.try_join_shared(&d, &private_id, &shares),
+            Err(Ok(TandaError::SharedSlotsUnavailable))
+        );
 
-        // Setup
-        let tanda_id = client.create_tanda(&creator, &name, &100_0000000, &5);
-        client.join_tanda(&delinquent, &tanda_id);
-        client.start_tanda(&creator, &tanda_id);
+        let mut config = client.default_config();
+        config.collateral_multiple = 1;
+        let name = String::from_str(&env, "Shared");
+        let tanda_id = client.create_tanda_with_config(&a, &name, &100_0000000, &3, &config);
+        client.join_tanda(&b, &tanda_id);
+
+        // Shares must cover the whole slot, among at least two owners
+        let mut shares = Map::new(&env);
+        shares.set(d.clone(), 6000);
+        assert_eq!(
+            client.try_join_shared(&d, &tanda_id, &shares),
+            Err(Ok(TandaError::InvalidOwnerCount))
+        );
+        shares.set(e.clone(), 3000);
+        assert_eq!(client.try_join_shared(&d, &tanda_id, &shares), Err(Ok(TandaError::InvalidShares)));
+        let mut huge = Map::new(&env);
+        huge.set(d.clone(), u32::MAX / 2 + 1);
+        huge.set(e.clone(), u32::MAX / 2 + 1);
+        assert_eq!(client.try_join_shared(&d, &tanda_id, &huge), Err(Ok(TandaError::InvalidShares)));
+        shares.set(e.clone(), 4000);
+        client.join_shared(&d, &tanda_id, &shares);
+        assert_eq!(client.get_collateral(&tanda_id).get(e.clone()), Some(40_0000000));
+
+        // Leaving gives up the slot for every co-owner, so they all sign
+        client.leave_tanda(&e, &tanda_id);
+        assert!(env.auths().iter().any(|(signer, _)| *signer == d));
+        assert_eq!(client.get_members(&tanda_id).len(), 2);
+        assert_eq!(client.get_collateral(&tanda_id).get(d.clone()), None);
+        client.join_shared(&d, &tanda_id, &shares);
+        client.start_tanda(&a, &tanda_id);
 
-        // Only creator deposits
+        let members = client.get_members(&tanda_id);
+        assert_eq!(members.len(), 3);
+        assert_eq!(members.get(2).unwrap().address, d);
+        assert_eq!(client.get_tanda(&tanda_id).total_cycles, 3);
+
+        // Each owner pays their share; the slot counts once both are in
+        for _ in 0..2 {
+            client.deposit(&a, &tanda_id);
+            client.deposit(&b, &tanda_id);
+            let before = token.balance(&d);
+            client.deposit(&d, &tanda_id);
+            assert_eq!(before - token.balance(&d), 60_3000000);
+            assert!(!client.all_deposited(&tanda_id));
+            assert_eq!(client.try_deposit(&d, &tanda_id), Err(Ok(TandaError::AlreadyDeposited)));
+            client.deposit(&e, &tanda_id);
+            client.trigger_payout(&tanda_id);
+        }
+
+        // E defaults on the slot's own cycle: only E is dropped, D's slot is
+        // still paid out and E's part of it goes to the safety fund
+        client.deposit(&a, &tanda_id);
+        client.deposit(&b, &tanda_id);
+        client.deposit(&d, &tanda_id);
+        keep_token_alive(&env, &eurc);
+        advance_days(&env, 7);
+        assert!(client.can_expel(&tanda_id, &e));
+        assert!(!client.can_expel(&tanda_id, &d));
+
+        let before_d = token.balance(&d);
+        let before_e = token.balance(&e);
+        client.advance(&tanda_id);
+        // D: 60% of the full pot (topped up from E's forfeited part), their
+        // bond, and the last third of the 80 left in the fund at completion
+        assert_eq!(token.balance(&d) - before_d, 180_0000000 + 60_0000000 + 26_6666668);
+        assert_eq!(token.balance(&e) - before_e, 40_0000000);
+
+        let slot = client.get_members(&tanda_id).get(2).unwrap();
+        assert_eq!(slot.status, MemberStatus::Received);
+        assert!(!slot.shares.contains_key(e.clone()));
+        let history = client.get_safety_fund_history(&tanda_id);
+        assert!(history.iter().any(|h| h.movement == FundMovement::Forfeit && h.amount == 104_0000000));
+
+        // What is left of the forfeited part goes back to the members still in
+        assert_eq!(client.get_tanda(&tanda_id).status, TandaStatus::Completed);
+        assert_eq!(client.get_safety_fund(&tanda_id), 0);
+        assert_eq!(client.get_collateral(&tanda_id).len(), 0);
+        assert_eq!(client.get_unallocated_balance(), 0);
+    }
+
+    #[test]
+    fn test_weighted_commitments() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
+
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let c = Address::generate(&env);
+        mint(&env, &eurc, &a, 2 * CREATE_TANDA_FEE + 1000_0000000);
+        mint(&env, &eurc, &b, 1000_0000000);
+        mint(&env, &eurc, &c, 1000_0000000);
+
+        // Pooled: each pays their own commitment and the beneficiary gets the sum
+        let mut config = client.default_config();
+        config.commitment_mode = CommitmentMode::Pooled;
+        let name = String::from_str(&env, "Pooled");
+        let tanda_id = client.create_tanda_with_config(&a, &name, &100_0000000, &3, &config);
+        client.join_tanda(&b, &tanda_id);
+        client.join_tanda(&c, &tanda_id);
+        assert_eq!(client.try_set_commitment(&b, &tanda_id, &0), Err(Ok(TandaError::InvalidAmount)));
+        client.set_commitment(&b, &tanda_id, &200_0000000);
+        client.set_commitment(&c, &tanda_id, &50_0000000);
+        client.start_tanda(&a, &tanda_id);
+        assert_eq!(
+            client.try_set_commitment(&c, &tanda_id, &60_0000000),
+            Err(Ok(TandaError::NotForming))
+        );
+        assert_eq!(
+            client.try_join_waitlist(&admin, &tanda_id),
+            Err(Ok(TandaError::WaitlistUnavailable))
+        );
+
+        let members = client.get_members(&tanda_id);
+        assert_eq!(members.get(1).unwrap().commitment, 200_0000000);
+
+        client.deposit(&a, &tanda_id);
+        let before = token.balance(&b);
+        client.deposit(&b, &tanda_id);
+        assert_eq!(before - token.balance(&b), 201_0000000);
+        client.deposit(&c, &tanda_id);
+        let before = token.balance(&a);
+        client.trigger_payout(&tanda_id);
+        assert_eq!(token.balance(&a) - before, 350_0000000);
+
+        // Proportional: payouts are commitment x slots, so over the rotation
+        // everyone gets back what they paid in, less commission
+        config.commitment_mode = CommitmentMode::Proportional;
+        config.payout_order = PayoutOrder::Auction;
+        let name = String::from_str(&env, "Proportional");
+        assert_eq!(
+            client.try_create_tanda_with_config(&a, &name, &100_0000000, &3, &config),
+            Err(Ok(TandaError::InvalidPayoutOrder))
+        );
+        config.payout_order = PayoutOrder::JoinOrder;
+        let tanda_id = client.create_tanda_with_config(&a, &name, &100_0000000, &3, &config);
+        client.join_tanda(&b, &tanda_id);
+        client.join_tanda(&c, &tanda_id);
+        client.set_commitment(&b, &tanda_id, &200_0000000);
+        client.start_tanda(&a, &tanda_id);
+        assert_eq!(client.try_prepay(&a, &tanda_id, &1), Err(Ok(TandaError::InvalidPrepayment)));
+
+        let start = [token.balance(&a), token.balance(&b), token.balance(&c)];
+        let pots = [300_0000000, 600_0000000, 300_0000000];
+        for (i, m) in [a.clone(), b.clone(), c.clone()].iter().enumerate() {
+            client.deposit(&a, &tanda_id);
+            client.deposit(&b, &tanda_id);
+            client.deposit(&c, &tanda_id);
+            assert_eq!(client.get_escrow(&tanda_id), pots[i]);
+            let before = token.balance(m);
+            client.trigger_payout(&tanda_id);
+            assert_eq!(token.balance(m) - before, pots[i]);
+        }
+
+        assert_eq!(client.get_tanda(&tanda_id).status, TandaStatus::Completed);
+        assert_eq!(start[0] - token.balance(&a), 1_5000000);
+        assert_eq!(start[1] - token.balance(&b), 3_0000000);
+        assert_eq!(start[2] - token.balance(&c), 1_5000000);
+        assert_eq!(client.get_unallocated_balance(), 0);
+    }
+
+    #[test]
+    fn test_renew_tanda() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
         let token = TokenClient::new(&env, &eurc);
-        token.mint(&creator, &200_0000000);
+
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let c = Address::generate(&env);
+        let d = Address::generate(&env);
+        let e = Address::generate(&env);
+        for m in [&a, &b, &c, &d, &e] {
+            mint(&env, &eurc, m, 1000_0000000);
+        }
+        mint(&env, &eurc, &a, CREATE_TANDA_FEE);
+
+        let mut config = client.default_config();
+        config.collateral_multiple = 1;
+        let name = String::from_str(&env, "Round one");
+        let tanda_id = client.create_tanda_with_config(&a, &name, &100_0000000, &3, &config);
+        client.join_tanda(&b, &tanda_id);
+        client.join_tanda(&c, &tanda_id);
+        client.start_tanda(&a, &tanda_id);
+        assert_eq!(
+            client.try_renew_tanda(&a, &tanda_id, &RenewalOrder::Keep),
+            Err(Ok(TandaError::NotCompleted))
+        );
+
+        // C is expelled in the first cycle; A and B finish the round
+        client.deposit(&a, &tanda_id);
+        client.deposit(&b, &tanda_id);
+        keep_token_alive(&env, &eurc);
+        advance_days(&env, 7);
+        client.advance(&tanda_id);
+        client.deposit(&a, &tanda_id);
+        client.deposit(&b, &tanda_id);
+        client.trigger_payout(&tanda_id);
+        assert_eq!(client.get_tanda(&tanda_id).status, TandaStatus::Completed);
+
+        // Renewing is free and takes the creator's seat right away
+        assert_eq!(
+            client.try_renew_tanda(&b, &tanda_id, &RenewalOrder::Keep),
+            Err(Ok(TandaError::NotCreator))
+        );
+        let before = token.balance(&a);
+        let next_id = client.renew_tanda(&a, &tanda_id, &RenewalOrder::Rotate);
+        assert_eq!(before - token.balance(&a), 100_0000000);
+        assert_eq!(
+            client.try_renew_tanda(&a, &tanda_id, &RenewalOrder::Keep),
+            Err(Ok(TandaError::AlreadyRenewed))
+        );
+        assert_eq!(client.get_tanda(&tanda_id).renewed_as, Some(next_id.clone()));
+        let next = client.get_tanda(&next_id);
+        assert_eq!(next.renewed_from, Some(tanda_id));
+        assert_eq!((next.amount, next.active_members), (100_0000000, 1));
+
+        // B's seat is held for them; C's is open to anyone
+        assert_eq!(client.try_confirm_seat(&c, &next_id), Err(Ok(TandaError::NoReservedSeat)));
+        assert_eq!(client.try_join_tanda(&b, &next_id), Err(Ok(TandaError::RequestPending)));
+        client.join_tanda(&d, &next_id);
+        assert_eq!(client.try_join_tanda(&e, &next_id), Err(Ok(TandaError::TandaFull)));
+        client.confirm_seat(&b, &next_id);
+        assert_eq!(client.get_collateral(&next_id).get(b.clone()), Some(100_0000000));
+
+        // Rotated: B was paid last in the first round, so goes first now
+        client.start_tanda(&a, &next_id);
+        assert_eq!(client.get_beneficiary(&next_id), b);
+        let members = client.get_members(&next_id);
+        assert_eq!(members.get(0).unwrap().position, 1);
+        assert_eq!(members.get(1).unwrap().position, 2);
+        assert_eq!(client.get_unallocated_balance(), 0);
+    }
+
+    #[test]
+    fn test_renewal_skips_member_expelled_from_any_slot() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+
+        client.initialize(&admin, &eurc, &commission, &0);
+
+        let creator = Address::generate(&env);
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        for m in [&creator, &a, &b] {
+            mint(&env, &eurc, m, 1000_0000000);
+        }
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+
+        let mut config = client.default_config();
+        config.max_slots_per_member = 2;
+        let name = String::from_str(&env, "Two slots");
+        let tanda_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &4, &config);
+        client.join_tanda(&a, &tanda_id);
+        client.join_tanda(&a, &tanda_id);
+        client.join_tanda(&b, &tanda_id);
+        client.start_tanda(&creator, &tanda_id);
+        for m in [&creator, &a, &b] {
+            client.deposit(m, &tanda_id);
+        }
+        client.trigger_payout(&tanda_id);
+
+        // A pays for their second slot only: the first is expelled before
+        // receiving and leaves the payout order, the second goes on
         client.deposit(&creator, &tanda_id);
+        client.deposit(&b, &tanda_id);
+        client.deposit_slot(&a, &tanda_id, &1);
+        keep_token_alive(&env, &eurc);
+        advance_days(&env, 7);
+        client.advance(&tanda_id);
+        for m in [&creator, &a, &b] {
+            client.deposit(m, &tanda_id);
+        }
+        client.trigger_payout(&tanda_id);
+        assert_eq!(client.get_tanda(&tanda_id).status, TandaStatus::Completed);
 
-        // Advance time 7 days
-        env.ledger().set(LedgerInfo {
-            timestamp: env.ledger().timestamp() + (7 * 86400),
-            protocol_version: 20,
-            sequence_number: 0,
-            network_id: Default::default(),
-            base_reserve: 10,
-            min_temp_entry_ttl: 0,
-            min_persistent_entry_ttl: 0,
-            max_entry_ttl: 0,
-        });
+        // A's remaining slot earns no seat
+        let next_id = client.renew_tanda(&creator, &tanda_id, &RenewalOrder::Keep);
+        assert_eq!(client.try_confirm_seat(&a, &next_id), Err(Ok(TandaError::NoReservedSeat)));
+        client.confirm_seat(&b, &next_id);
+        assert_eq!(client.get_tanda(&next_id).active_members, 2);
+    }
 
-        // Can expel
-        assert!(client.can_expel(&tanda_id, &delinquent));
+    #[test]
+    fn test_renewed_lottery_keeps_drawing() {
+        let (env, admin, eurc, commission) = setup_env();
 
-        // Expel
-        client.expel_delinquent(&tanda_id, &delinquent);
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        let token = TokenClient::new(&env, &eurc);
 
-        // Check expelled
-        let members = client.get_members(&tanda_id);
+        client.initialize(&admin, &eurc, &commission, &0);
+
+        let members = Vec::from_array(&env, [
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+            Address::generate(&env),
+        ]);
+        let creator = members.get(0).unwrap();
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
         for m in members.iter() {
-            if m.address == delinquent {
-                assert_eq!(m.status, MemberStatus::Expelled);
+            mint(&env, &eurc, &m, 400_0000000);
+        }
+
+        let mut config = client.default_config();
+        config.payout_order = PayoutOrder::Lottery;
+        let name = String::from_str(&env, "Lottery");
+        let tanda_id = client.create_tanda_with_config(&creator, &name, &100_0000000, &4, &config);
+        for m in members.iter().skip(1) {
+            client.join_tanda(&m, &tanda_id);
+        }
+        client.start_tanda(&creator, &tanda_id);
+        for _ in 0..4 {
+            for m in members.iter() {
+                client.deposit(&m, &tanda_id);
+            }
+            client.trigger_payout(&tanda_id);
+        }
+        let next_id = client.renew_tanda(&creator, &tanda_id, &RenewalOrder::Keep);
+        assert_eq!(client.get_tanda(&next_id).payout_order, PayoutOrder::Lottery);
+
+        // Seats are taken in the order confirmed: the draw decides who is paid
+        for i in [3, 2, 1] {
+            client.confirm_seat(&members.get(i).unwrap(), &next_id);
+        }
+        client.start_tanda(&creator, &next_id);
+        let seated = client.get_members(&next_id);
+        for (slot, i) in [0, 3, 2, 1].iter().enumerate() {
+            assert_eq!(seated.get(slot as u32).unwrap().address, members.get(*i).unwrap());
+        }
+
+        let mut winners = Vec::new(&env);
+        for _ in 0..4 {
+            for m in members.iter() {
+                client.deposit(&m, &next_id);
+            }
+            let mut before = [0i128; 4];
+            for (i, m) in members.iter().enumerate() {
+                before[i] = token.balance(&m);
+            }
+            client.trigger_payout(&next_id);
+            for (i, m) in members.iter().enumerate() {
+                if token.balance(&m) > before[i] {
+                    winners.push_back(m);
+                }
+            }
+        }
+        for m in members.iter() {
+            assert!(winners.contains(&m));
+            assert_eq!(token.balance(&m), 400_0000000);
+        }
+    }
+
+    #[test]
+    fn test_renewed_reverse_tanda_keeps_reserved_order() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+
+        client.initialize(&admin, &eurc, &commission, &0);
+
+        let a = Address::generate(&env);
+        let b = Address::generate(&env);
+        let c = Address::generate(&env);
+        let d = Address::generate(&env);
+        for m in [&a, &b, &c, &d] {
+            mint(&env, &eurc, m, 1000_0000000);
+        }
+        mint(&env, &eurc, &a, CREATE_TANDA_FEE);
+
+        // Reverse: C, B, A
+        let mut config = client.default_config();
+        config.payout_order = PayoutOrder::Reverse;
+        let name = String::from_str(&env, "Reverse");
+        let tanda_id = client.create_tanda_with_config(&a, &name, &100_0000000, &4, &config);
+        client.join_tanda(&b, &tanda_id);
+        client.join_tanda(&c, &tanda_id);
+        client.start_tanda(&a, &tanda_id);
+        for _ in 0..3 {
+            for m in [&a, &b, &c] {
+                client.deposit(m, &tanda_id);
+            }
+            client.trigger_payout(&tanda_id);
+        }
+
+        // Kept: C, B, A again, then the newcomer
+        let next_id = client.renew_tanda(&a, &tanda_id, &RenewalOrder::Keep);
+        assert_eq!(client.get_tanda(&next_id).payout_order, PayoutOrder::Reverse);
+        client.confirm_seat(&b, &next_id);
+        client.join_tanda(&d, &next_id);
+        client.confirm_seat(&c, &next_id);
+        client.start_tanda(&a, &next_id);
+
+        let members = client.get_members(&next_id);
+        let order = [&c, &b, &a, &d];
+        for m in members.iter() {
+            assert_eq!(m.address, *order[m.position as usize]);
+        }
+        assert_eq!(client.get_beneficiary(&next_id), c);
+    }
+
+    #[test]
+    fn test_active_tanda_survives_full_rotation() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let mut members = Vec::new(&env);
+        for _ in 0..12 {
+            let m = Address::generate(&env);
+            mint(&env, &eurc, &m, 200_0000000);
+            members.push_back(m);
+        }
+        let creator = members.get(0).unwrap();
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+
+        let mut config = client.default_config();
+        config.delinquency_days = MAX_DELINQUENCY_DAYS;
+        let name = String::from_str(&env, "Long haul");
+        let tanda_id = client.create_tanda_with_config(&creator, &name, &10_0000000, &12, &config);
+        for m in members.iter().skip(1) {
+            client.join_tanda(&m, &tanda_id);
+        }
+        client.start_tanda(&creator, &tanda_id);
+
+        // Each cycle everyone pays at the very end of a 14-day window. The
+        // budget is reset per call so each one is held to a transaction's limits
+        for _ in 0..12 {
+            keep_token_alive(&env, &eurc);
+            advance_days(&env, 14);
+            for m in members.iter() {
+                env.budget().reset_default();
+                client.deposit(&m, &tanda_id);
+            }
+            env.budget().reset_default();
+            client.advance(&tanda_id);
+            assert!(client.get_ttl(&tanda_id) >= TANDA_LIFETIME_THRESHOLD);
+        }
+
+        let tanda = client.get_tanda(&tanda_id);
+        assert_eq!(tanda.status, TandaStatus::Completed);
+        assert_eq!(tanda.current_cycle, 13);
+
+        let ttl = env.as_contract(&contract_id, || {
+            env.storage().persistent().get_ttl(&DataKey::Member(tanda_id.clone(), creator.clone()))
+        });
+        assert!(ttl >= TANDA_LIFETIME_THRESHOLD);
+    }
+
+    #[test]
+    fn test_bump_keeps_idle_tanda_alive() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        let creator = Address::generate(&env);
+        mint(&env, &eurc, &creator, CREATE_TANDA_FEE);
+        let tanda_id = client.create_tanda(&creator, &String::from_str(&env, "Idle"), &100_0000000, &5);
+        assert_eq!(client.get_ttl(&tanda_id), TANDA_LIFETIME_THRESHOLD);
+
+        // Nobody touches the tanda for 100 days; a keeper bumps it twice
+        for _ in 0..2 {
+            advance_days(&env, 100);
+            assert_eq!(client.get_ttl(&tanda_id), 0);
+            client.bump(&tanda_id);
+            assert_eq!(client.get_ttl(&tanda_id), TANDA_LIFETIME_THRESHOLD);
+        }
+
+        assert_eq!(client.get_tanda(&tanda_id).status, TandaStatus::Forming);
+    }
+
+    /// Create and start a tanda of `size` funded members; returns its id and members
+    fn start_funded_tanda(
+        env: &Env,
+        client: &TandaContractClient,
+        eurc: &Address,
+        size: u32,
+        mode: CommitmentMode,
+    ) -> (String, Vec<Address>) {
+        let mut members = Vec::new(env);
+        for _ in 0..size {
+            let m = Address::generate(env);
+            mint(env, eurc, &m, 200_0000000);
+            members.push_back(m);
+        }
+        let creator = members.get(0).unwrap();
+        mint(env, eurc, &creator, CREATE_TANDA_FEE);
+
+        let mut config = client.default_config();
+        config.commitment_mode = mode;
+        let name = String::from_str(env, "Budget");
+        let tanda_id = client.create_tanda_with_config(&creator, &name, &10_0000000, &size, &config);
+        for m in members.iter().skip(1) {
+            client.join_tanda(&m, &tanda_id);
+        }
+        client.start_tanda(&creator, &tanda_id);
+
+        (tanda_id, members)
+    }
+
+    #[test]
+    fn test_deposit_cost_independent_of_member_count() {
+        let (env, admin, eurc, commission) = setup_env();
+
+        let contract_id = env.register_contract(None, TandaContract);
+        let client = TandaContractClient::new(&env, &contract_id);
+        client.initialize(&admin, &eurc, &commission, &50);
+
+        // Both tandas of a mode live in the same ledger so only their layout differs
+        for mode in [CommitmentMode::Equal, CommitmentMode::Proportional] {
+            let (pair_id, pair) = start_funded_tanda(&env, &client, &eurc, 2, mode.clone());
+            let (full_id, full) = start_funded_tanda(&env, &client, &eurc, 12, mode.clone());
+            client.deposit(&pair.get(0).unwrap(), &pair_id);
+            for m in full.iter().take(11) {
+                client.deposit(&m, &full_id);
             }
+
+            // Budget of the last deposit of a cycle and of the all_deposited check
+            let measure = |user: Address, tanda_id: &String| {
+                env.budget().reset_default();
+                client.deposit(&user, tanda_id);
+                let deposit = (env.budget().cpu_instruction_cost(), env.budget().memory_bytes_cost());
+
+                env.budget().reset_default();
+                assert!(client.all_deposited(tanda_id));
+                (deposit, env.budget().cpu_instruction_cost())
+            };
+            let ((pair_cpu, pair_mem), pair_check) = measure(pair.get(1).unwrap(), &pair_id);
+            let ((full_cpu, full_mem), full_check) = measure(full.get(11).unwrap(), &full_id);
+
+            // With one record per member a deposit only reads and writes the
+            // depositor's entries, so a full 12-member tanda costs the same as a
+            // pair. The old Vec<Member> layout rewrote every member on each deposit,
+            // and proportional dues once read every commitment.
+            assert!(full_cpu <= pair_cpu * 102 / 100, "cpu {} vs {}", full_cpu, pair_cpu);
+            assert!(full_mem <= pair_mem * 102 / 100, "mem {} vs {}", full_mem, pair_mem);
+            assert!(full_check <= pair_check * 102 / 100, "check {} vs {}", full_check, pair_check);
         }
     }
 }